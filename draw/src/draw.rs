@@ -1,4 +1,24 @@
+pub mod box_drawing;
 pub mod canvas;
 pub mod character;
+pub mod color;
+pub mod diff;
+pub mod document;
+pub mod ellipse;
+pub mod figlet;
+pub mod fill;
+pub mod grapheme;
+pub mod history;
+pub mod line;
+pub mod mirror;
+pub mod palette;
+pub mod pixel;
+pub mod quantize;
+pub mod rect;
+pub mod rectangle;
+pub mod region;
+pub mod rotation;
 pub mod style;
-
+#[cfg(test)]
+pub(crate) mod test_helpers;
+pub mod text;
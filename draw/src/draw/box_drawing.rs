@@ -0,0 +1,222 @@
+use Weight::{Empty as E, Light as L, Heavy as H, Double as D};
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum Weight {
+    Empty,
+    Light,
+    Heavy,
+    Double
+}
+
+// Arms are listed clockwise: up, right, down, left.
+// Dashed and rounded glyphs come after their plain counterparts so lookups by arms prefer the plain ones.
+const GLYPHS: [(char, [Weight; 4]); 125] = [
+    ('─', [E, L, E, L]),
+    ('━', [E, H, E, H]),
+    ('│', [L, E, L, E]),
+    ('┃', [H, E, H, E]),
+    ('┄', [E, L, E, L]),
+    ('┅', [E, H, E, H]),
+    ('┆', [L, E, L, E]),
+    ('┇', [H, E, H, E]),
+    ('┈', [E, L, E, L]),
+    ('┉', [E, H, E, H]),
+    ('┊', [L, E, L, E]),
+    ('┋', [H, E, H, E]),
+    ('┌', [E, L, L, E]),
+    ('┍', [E, H, L, E]),
+    ('┎', [E, L, H, E]),
+    ('┏', [E, H, H, E]),
+    ('┐', [E, E, L, L]),
+    ('┑', [E, E, L, H]),
+    ('┒', [E, E, H, L]),
+    ('┓', [E, E, H, H]),
+    ('└', [L, L, E, E]),
+    ('┕', [L, H, E, E]),
+    ('┖', [H, L, E, E]),
+    ('┗', [H, H, E, E]),
+    ('┘', [L, E, E, L]),
+    ('┙', [L, E, E, H]),
+    ('┚', [H, E, E, L]),
+    ('┛', [H, E, E, H]),
+    ('├', [L, L, L, E]),
+    ('┝', [L, H, L, E]),
+    ('┞', [H, L, L, E]),
+    ('┟', [L, L, H, E]),
+    ('┠', [H, L, H, E]),
+    ('┡', [H, H, L, E]),
+    ('┢', [L, H, H, E]),
+    ('┣', [H, H, H, E]),
+    ('┤', [L, E, L, L]),
+    ('┥', [L, E, L, H]),
+    ('┦', [H, E, L, L]),
+    ('┧', [L, E, H, L]),
+    ('┨', [H, E, H, L]),
+    ('┩', [H, E, L, H]),
+    ('┪', [L, E, H, H]),
+    ('┫', [H, E, H, H]),
+    ('┬', [E, L, L, L]),
+    ('┭', [E, L, L, H]),
+    ('┮', [E, H, L, L]),
+    ('┯', [E, H, L, H]),
+    ('┰', [E, L, H, L]),
+    ('┱', [E, L, H, H]),
+    ('┲', [E, H, H, L]),
+    ('┳', [E, H, H, H]),
+    ('┴', [L, L, E, L]),
+    ('┵', [L, L, E, H]),
+    ('┶', [L, H, E, L]),
+    ('┷', [L, H, E, H]),
+    ('┸', [H, L, E, L]),
+    ('┹', [H, L, E, H]),
+    ('┺', [H, H, E, L]),
+    ('┻', [H, H, E, H]),
+    ('┼', [L, L, L, L]),
+    ('┽', [L, L, L, H]),
+    ('┾', [L, H, L, L]),
+    ('┿', [L, H, L, H]),
+    ('╀', [H, L, L, L]),
+    ('╁', [L, L, H, L]),
+    ('╂', [H, L, H, L]),
+    ('╃', [H, L, L, H]),
+    ('╄', [H, H, L, L]),
+    ('╅', [L, L, H, H]),
+    ('╆', [L, H, H, L]),
+    ('╇', [H, H, L, H]),
+    ('╈', [L, H, H, H]),
+    ('╉', [H, L, H, H]),
+    ('╊', [H, H, H, L]),
+    ('╋', [H, H, H, H]),
+    ('╌', [E, L, E, L]),
+    ('╍', [E, H, E, H]),
+    ('╎', [L, E, L, E]),
+    ('╏', [H, E, H, E]),
+    ('═', [E, D, E, D]),
+    ('║', [D, E, D, E]),
+    ('╒', [E, D, L, E]),
+    ('╓', [E, L, D, E]),
+    ('╔', [E, D, D, E]),
+    ('╕', [E, E, L, D]),
+    ('╖', [E, E, D, L]),
+    ('╗', [E, E, D, D]),
+    ('╘', [L, D, E, E]),
+    ('╙', [D, L, E, E]),
+    ('╚', [D, D, E, E]),
+    ('╛', [L, E, E, D]),
+    ('╜', [D, E, E, L]),
+    ('╝', [D, E, E, D]),
+    ('╞', [L, D, L, E]),
+    ('╟', [D, L, D, E]),
+    ('╠', [D, D, D, E]),
+    ('╡', [L, E, L, D]),
+    ('╢', [D, E, D, L]),
+    ('╣', [D, E, D, D]),
+    ('╤', [E, D, L, D]),
+    ('╥', [E, L, D, L]),
+    ('╦', [E, D, D, D]),
+    ('╧', [L, D, E, D]),
+    ('╨', [D, L, E, L]),
+    ('╩', [D, D, E, D]),
+    ('╪', [L, D, L, D]),
+    ('╫', [D, L, D, L]),
+    ('╬', [D, D, D, D]),
+    ('╭', [E, L, L, E]),
+    ('╮', [E, E, L, L]),
+    ('╯', [L, E, E, L]),
+    ('╰', [L, L, E, E]),
+    ('╴', [E, E, E, L]),
+    ('╵', [L, E, E, E]),
+    ('╶', [E, L, E, E]),
+    ('╷', [E, E, L, E]),
+    ('╸', [E, E, E, H]),
+    ('╹', [H, E, E, E]),
+    ('╺', [E, H, E, E]),
+    ('╻', [E, E, H, E]),
+    ('╼', [E, H, E, L]),
+    ('╽', [L, E, H, E]),
+    ('╾', [E, L, E, H]),
+    ('╿', [H, E, L, E]),
+];
+
+pub fn arms(glyph: char) -> Option<[Weight; 4]> {
+    GLYPHS.iter().find(|(c, _)| *c == glyph).map(|(_, a)| *a)
+}
+
+pub fn glyph(arms: [Weight; 4]) -> Option<char> {
+    GLYPHS.iter().find(|(_, a)| *a == arms).map(|(c, _)| *c)
+}
+
+pub fn merge(existing: char, incoming: char) -> Option<char> {
+    let existing = arms(existing)?;
+    let incoming = arms(incoming)?;
+
+    let mut merged = existing;
+    for (m, i) in merged.iter_mut().zip(incoming) {
+        if i != E {
+            *m = i;
+        }
+    }
+
+    glyph(merged)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_box_drawing_arms() {
+        assert_eq!(arms('┼'), Some([L, L, L, L]));
+        assert_eq!(arms('╞'), Some([L, D, L, E]));
+        assert_eq!(arms('┭'), Some([E, L, L, H]));
+        assert_eq!(arms('╭'), Some([E, L, L, E]));
+        assert_eq!(arms('a'), None);
+    }
+
+    #[test]
+    fn test_box_drawing_glyph_prefers_plain() {
+        assert_eq!(glyph([E, L, E, L]), Some('─'));
+        assert_eq!(glyph([E, L, L, E]), Some('┌'));
+        assert_eq!(glyph([H, D, E, E]), None);
+    }
+
+    #[test]
+    fn test_box_drawing_merge_light() {
+        assert_eq!(merge('│', '─'), Some('┼'));
+        assert_eq!(merge('─', '│'), Some('┼'));
+        assert_eq!(merge('│', '╶'), Some('├'));
+        assert_eq!(merge('│', '╴'), Some('┤'));
+        assert_eq!(merge('─', '╷'), Some('┬'));
+        assert_eq!(merge('─', '╵'), Some('┴'));
+        assert_eq!(merge('┌', '┘'), Some('┼'));
+        assert_eq!(merge('╭', '─'), Some('┬'));
+    }
+
+    #[test]
+    fn test_box_drawing_merge_double_heavy_mixed() {
+        assert_eq!(merge('║', '═'), Some('╬'));
+        assert_eq!(merge('║', '─'), Some('╫'));
+        assert_eq!(merge('╔', '│'), Some('╞'));
+        assert_eq!(merge('┃', '━'), Some('╋'));
+        assert_eq!(merge('│', '━'), Some('┿'));
+        assert_eq!(merge('┏', '┃'), Some('┣'));
+    }
+
+    #[test]
+    fn test_box_drawing_merge_incoming_weight_wins() {
+        assert_eq!(merge('─', '━'), Some('━'));
+        assert_eq!(merge('┼', '═'), Some('╪'));
+    }
+
+    #[test]
+    fn test_box_drawing_merge_unrepresentable() {
+        assert_eq!(merge('║', '━'), None);
+        assert_eq!(merge('a', '─'), None);
+        assert_eq!(merge('─', '#'), None);
+    }
+}
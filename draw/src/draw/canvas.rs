@@ -1,57 +1,271 @@
+use std::fmt::Display;
+
 use crate::draw::character::Character;
 
-use super::character::BlendMode;
+use super::{character::BlendMode, rect::Rect};
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum CanvasError {
+    NotRectangular { row: usize, width: usize, expected: usize }
+}
+
+impl Display for CanvasError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CanvasError::NotRectangular { row, width, expected } => {
+                write!(f, "Canvas row {row} is {width} cells wide, expected {expected}")
+            }
+        }
+    }
+}
 
+impl std::error::Error for CanvasError {}
+
+
+// Cells are stored row by row, `width` cells per row.
 #[derive(Debug)]
 pub struct Canvas {
-    buffer: Vec<Vec<Character>>
+    cells: Vec<Character>,
+    width: usize,
+    height: usize
+}
+
+// Reversing a row puts continuations in front of their wide glyphs.
+pub(crate) fn swap_reversed_wide(cells: &mut [Character]) {
+    for x in 1..cells.len() {
+        if cells[x].is_wide() && cells[x - 1].is_continuation() {
+            cells.swap(x - 1, x);
+        }
+    }
+}
+
+// Blanks halves of wide glyphs that lost their partner, like the ones at the edges of a copied region.
+pub(crate) fn repair_wide(cells: &mut [Character]) {
+    for x in 0..cells.len() {
+        if cells[x].is_continuation() && !(x > 0 && cells[x - 1].is_wide()) {
+            cells[x].set_continuation(false);
+        } else if cells[x].is_wide() && !cells.get(x + 1).is_some_and(Character::is_continuation) {
+            cells[x].set_character(' ');
+        }
+    }
 }
 
 impl Canvas {
     pub fn new_with_buffer(buffer: Vec<Vec<Character>>) -> Self {
-        let width = buffer[0].len();
+        let width = buffer.first().map_or(0, Vec::len);
         for l in &buffer {
             assert_eq!(l.len(), width, "Canvas buffer should be rectangular");
         }
 
-        Self { buffer }
+        Self {
+            width,
+            height: buffer.len(),
+            cells: buffer.into_iter().flatten().collect()
+        }
+    }
+
+    pub fn try_new_with_buffer(buffer: Vec<Vec<Character>>) -> Result<Self, CanvasError> {
+        let expected = buffer.first().map_or(0, Vec::len);
+        if let Some((row, l)) = buffer.iter().enumerate().find(|(_, l)| l.len() != expected) {
+            return Err(CanvasError::NotRectangular { row, width: l.len(), expected });
+        }
+
+        Ok(Self::new_with_buffer(buffer))
     }
 
     pub fn new_filled(width: usize, height: usize, character: Character) -> Self {
-        Self::new_with_buffer(
-            vec![vec![character; width]; height]
-        )
+        Self {
+            cells: vec![character; width * height],
+            width,
+            height
+        }
     }
 
     pub fn new(width: usize, height: usize) -> Self {
         Self::new_filled(width, height, Character::default())
     }
 
-    pub fn buffer(&self) -> &[Vec<Character>] {
-        self.buffer.as_slice()
+    pub fn get(&self, x: usize, y: usize) -> Option<&Character> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Character> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[Character] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub(crate) fn row_mut(&mut self, y: usize) -> &mut [Character] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Character]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn cells(&self) -> &[Character] {
+        self.cells.as_slice()
+    }
+
+    pub(crate) fn cells_mut(&mut self) -> &mut [Character] {
+        self.cells.as_mut_slice()
+    }
+
+    pub fn enumerate_cells(&self) -> impl Iterator<Item = ((usize, usize), &Character)> + '_ {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, c)| ((i % width, i / width), c))
+    }
+
+    pub fn iter_region(&self, region: Rect) -> impl Iterator<Item = ((usize, usize), &Character)> + '_ {
+        let region = region.intersection(&self.bounds());
+        (region.y..region.bottom()).flat_map(move |y| {
+            (region.x..region.right()).map(move |x| ((x, y), &self.row(y)[x]))
+        })
+    }
+
+    pub fn for_each_cell(&mut self, mut f: impl FnMut((usize, usize), &mut Character)) {
+        let width = self.width;
+        for (i, c) in self.cells.iter_mut().enumerate() {
+            f((i % width, i / width), c);
+        }
+    }
+
+    pub fn map_cells(&self, mut f: impl FnMut((usize, usize), &Character) -> Character) -> Canvas {
+        Canvas {
+            cells: self.enumerate_cells().map(|(p, c)| f(p, c)).collect(),
+            width: self.width,
+            height: self.height
+        }
+    }
+
+    // Cells are no longer stored as rows, so this has to copy them.
+    #[deprecated(note = "use `rows()` to borrow the rows or `to_buffer()` to copy them")]
+    pub fn buffer(&self) -> Vec<Vec<Character>> {
+        self.to_buffer()
+    }
+
+    pub fn to_buffer(&self) -> Vec<Vec<Character>> {
+        self.rows().map(|r| r.to_vec()).collect()
     }
 
     pub fn width(&self) -> usize {
-        self.buffer[0].len()
+        self.width
     }
 
     pub fn height(&self) -> usize {
-        self.buffer.len()
+        self.height
+    }
+
+    pub fn bounds(&self) -> Rect {
+        Rect::new(0, 0, self.width(), self.height())
     }
 
     pub fn resize(&mut self, width: usize, height: usize) {
-        self.buffer.resize(height, vec![Character::default(); self.width()]);
-        for r in self.buffer.iter_mut() {
-            r.resize(width, Character::default())
+        self.resize_filled(width, height, &Character::default());
+    }
+
+    pub fn resize_filled(&mut self, width: usize, height: usize, fill: &Character) {
+        if width != self.width {
+            let mut cells = Vec::with_capacity(width * height);
+            for y in 0..self.height.min(height) {
+                cells.extend_from_slice(&self.row(y)[..width.min(self.width)]);
+                cells.resize((y + 1) * width, fill.clone());
+                if let Some(c) = cells.last_mut().filter(|c| c.is_wide()) {
+                    c.set_character(' ');
+                }
+            }
+            self.cells = cells;
         }
+
+        self.cells.resize(width * height, fill.clone());
+        self.width = width;
+        self.height = height;
+    }
+
+    // Blanks both halves of a wide glyph that sits across the left edge of `point`.
+    pub(crate) fn split_wide_at(&mut self, point: (usize, usize)) {
+        let (x, y) = point;
+        if x == 0 || !self.get(x, y).is_some_and(Character::is_continuation) {
+            return;
+        }
+
+        let l = self.row_mut(y);
+        l[x].set_continuation(false);
+        l[x - 1].set_character(' ');
     }
 
     pub fn draw_character(&mut self, point: (usize, usize), character: &Character, mode: BlendMode) {
-        if let Some(l) = self.buffer.get_mut(point.1) {
-            if let Some(c) = l.get_mut(point.0) {
-                c.blend(character, mode)
+        let (x, y) = point;
+        // Continuations are written together with the wide glyph they belong to.
+        if character.is_continuation() || y >= self.height() || x >= self.width() {
+            return;
+        }
+
+        let width = self.width();
+        let l = self.row_mut(y);
+        let old = l[x].clone();
+
+        // A wide brush moved one cell at a time lands on its own right half, which would otherwise break it up.
+        if old.is_continuation() && x > 0 {
+            let mut repainted = l[x - 1].clone();
+            repainted.blend(character, mode);
+            if repainted == l[x - 1] {
+                return;
+            }
+        }
+        let mut new = old.clone();
+        new.blend(character, mode);
+
+        let partner = if old.is_continuation() {
+            x.checked_sub(1)
+        } else if old.is_wide() {
+            Some(x + 1).filter(|p| *p < width)
+        } else {
+            None
+        };
+
+        if new.character() == old.character() && new.is_continuation() == old.is_continuation() {
+            // Only the style changed, keep both halves of a wide glyph in sync.
+            if let Some(p) = partner {
+                l[p].blend(&Character::new_transparent(*character.style()), BlendMode::OnlyStyle);
+            }
+            l[x] = new;
+            return;
+        }
+
+        if let Some(p) = partner {
+            if old.is_continuation() {
+                l[p].set_character(' ');
+            } else {
+                l[p].set_continuation(false);
             }
         }
+
+        if new.is_wide() {
+            if x + 1 == width {
+                new.set_character(' ');
+            } else {
+                if l[x + 1].is_wide() && x + 2 < width {
+                    l[x + 2].set_continuation(false);
+                }
+                l[x + 1] = Character::new_continuation(*new.style());
+            }
+        }
+        l[x] = new;
     }
 }
 
@@ -87,7 +301,7 @@ mod test {
             ]
         ];
         assert_eq!(
-            Canvas::new_with_buffer(buffer.clone()).buffer,
+            Canvas::new_with_buffer(buffer.clone()).to_buffer(),
             buffer
         );
     }
@@ -117,20 +331,69 @@ mod test {
         Canvas::new_with_buffer(buffer);
     }
 
+    #[test]
+    fn test_try_new_with_buffer() {
+        let a = Character::new('a', Style::default());
+        let c = Canvas::try_new_with_buffer(vec![vec![a.clone(); 3]; 2]).unwrap();
+
+        assert_eq!(c.width(), 3);
+        assert_eq!(c.height(), 2);
+        assert_eq!(c.to_buffer(), vec![vec![a.clone(); 3]; 2]);
+
+        assert_eq!(
+            Canvas::try_new_with_buffer(vec![vec![a.clone(); 3], vec![a.clone(); 3], vec![a; 2]]).unwrap_err(),
+            CanvasError::NotRectangular { row: 2, width: 2, expected: 3 }
+        );
+    }
+
+    #[test]
+    fn test_canvas_empty() {
+        let c = Canvas::try_new_with_buffer(Vec::new()).unwrap();
+        assert_eq!((c.width(), c.height()), (0, 0));
+        assert!(c.to_buffer().is_empty());
+
+        let c = Canvas::new_with_buffer(vec![Vec::new(); 3]);
+        assert_eq!((c.width(), c.height()), (0, 3));
+        assert_eq!(c.row(2), &[]);
+
+        let mut c = Canvas::new(4, 0);
+        assert_eq!((c.width(), c.height()), (4, 0));
+        assert_eq!(c.bounds(), Rect::new(0, 0, 4, 0));
+        assert!(c.get(0, 0).is_none());
+
+        c.draw_character((0, 0), &Character::new('#', Style::default()), BlendMode::Overwrite);
+        c.resize(2, 1);
+        assert_eq!(c.to_buffer(), vec![vec![Character::default(); 2]]);
+    }
+
+    #[test]
+    fn test_canvas_get() {
+        let a = Character::new('a', Style::default());
+        let mut c = Canvas::new(3, 2);
+
+        *c.get_mut(2, 1).unwrap() = a.clone();
+
+        assert_eq!(c.get(2, 1), Some(&a));
+        assert_eq!(c.get(0, 0), Some(&Character::default()));
+        assert_eq!(c.get(3, 0), None);
+        assert_eq!(c.get(0, 2), None);
+        assert!(c.get_mut(3, 1).is_none());
+    }
+
     #[test]
     fn test_canvas_new_filled() {
         let c = Character::new('#', Style::new(Color::DarkCyan, Color::Red, Modifiers::new(true, true, false)));
         assert_eq!(
-            Canvas::new_filled(3, 2, c).buffer,
+            Canvas::new_filled(3, 2, c.clone()).to_buffer(),
             vec![
                 vec![
-                    c,
-                    c,
-                    c
+                    c.clone(),
+                    c.clone(),
+                    c.clone()
                 ],
                 vec![
-                    c,
-                    c,
+                    c.clone(),
+                    c.clone(),
                     c
                 ]
             ]
@@ -140,7 +403,7 @@ mod test {
     #[test]
     fn test_canvas_new() {
         assert_eq!(
-            Canvas::new(3, 2).buffer,
+            Canvas::new(3, 2).to_buffer(),
             vec![
                 vec![
                     Character::default(),
@@ -176,7 +439,7 @@ mod test {
     fn test_canvas_draw_overflow() {
        let fill = Character::new('_', Style::new(Color::Red, Color::Black, Modifiers::new(false, false, false)));
         let brush = Character::new('#', Style::new(Color::DarkCyan, Color::Red, Modifiers::new(true, true, false)));
-        let mut c = Canvas::new_filled(3, 2, fill);
+        let mut c = Canvas::new_filled(3, 2, fill.clone());
 
         c.draw_character(
             (10, 10),
@@ -185,16 +448,16 @@ mod test {
         );
 
         assert_eq!(
-            c.buffer,
+            c.to_buffer(),
             vec![
                 vec![
-                    fill,
-                    fill,
-                    fill
+                    fill.clone(),
+                    fill.clone(),
+                    fill.clone()
                 ],
                 vec![
-                    fill,
-                    fill,
+                    fill.clone(),
+                    fill.clone(),
                     fill
                 ]
             ]
@@ -205,7 +468,7 @@ mod test {
     fn test_canvas_draw_overwrite() {
        let fill = Character::new('_', Style::new(Color::Red, Color::Black, Modifiers::new(false, false, false)));
         let brush = Character::new('#', Style::new(Color::DarkCyan, Color::Red, Modifiers::new(true, true, false)));
-        let mut c = Canvas::new_filled(3, 2, fill);
+        let mut c = Canvas::new_filled(3, 2, fill.clone());
 
         c.draw_character(
             (2, 1),
@@ -214,15 +477,15 @@ mod test {
         );
 
         assert_eq!(
-            c.buffer,
+            c.to_buffer(),
             vec![
                 vec![
-                    fill,
-                    fill,
-                    fill
+                    fill.clone(),
+                    fill.clone(),
+                    fill.clone()
                 ],
                 vec![
-                    fill,
+                    fill.clone(),
                     fill,
                     brush
                 ]
@@ -234,7 +497,7 @@ mod test {
     fn test_canvas_draw_only_character() {
         let fill = Character::new('_', Style::new(Color::Red, Color::Black, Modifiers::new(false, false, false)));
         let brush = Character::new('#', Style::new(Color::DarkCyan, Color::Red, Modifiers::new(true, true, false)));
-        let mut c = Canvas::new_filled(3, 2, fill);
+        let mut c = Canvas::new_filled(3, 2, fill.clone());
 
         c.draw_character(
             (0, 0),
@@ -243,16 +506,16 @@ mod test {
         );
 
         assert_eq!(
-            c.buffer,
+            c.to_buffer(),
             vec![
                 vec![
                     Character::new(brush.character(), *fill.style()),
-                    fill,
-                    fill
+                    fill.clone(),
+                    fill.clone()
                 ],
                 vec![
-                    fill,
-                    fill,
+                    fill.clone(),
+                    fill.clone(),
                     fill
                 ]
             ]
@@ -263,7 +526,7 @@ mod test {
     fn test_canvas_draw_only_style() {
         let fill = Character::new('_', Style::new(Color::Red, Color::Black, Modifiers::new(false, false, false)));
         let brush = Character::new('#', Style::new(Color::DarkCyan, Color::Red, Modifiers::new(true, true, false)));
-        let mut c = Canvas::new_filled(3, 2, fill);
+        let mut c = Canvas::new_filled(3, 2, fill.clone());
 
         c.draw_character(
             (1, 1),
@@ -272,15 +535,15 @@ mod test {
         );
 
         assert_eq!(
-            c.buffer,
+            c.to_buffer(),
             vec![
                 vec![
-                    fill,
-                    fill,
-                    fill
+                    fill.clone(),
+                    fill.clone(),
+                    fill.clone()
                 ],
                 vec![
-                    fill,
+                    fill.clone(),
                     Character::new(fill.character(), *brush.style()),
                     fill
                 ]
@@ -288,36 +551,289 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_canvas_draw_merge_lines() {
+        let vertical = Character::new('│', Style::default());
+        let horizontal = Character::new('─', Style::new(Color::Red, Color::None, Modifiers::default()));
+        let mut c = Canvas::new_filled(3, 2, vertical.clone());
+
+        c.draw_character(
+            (1, 1),
+            &horizontal,
+            BlendMode::MergeLines
+        );
+
+        assert_eq!(
+            c.to_buffer(),
+            vec![
+                vec![
+                    vertical.clone(),
+                    vertical.clone(),
+                    vertical.clone()
+                ],
+                vec![
+                    vertical.clone(),
+                    Character::new('┼', *horizontal.style()),
+                    vertical
+                ]
+            ]
+        )
+    }
+
+    #[test]
+    fn test_canvas_draw_wide() {
+        let fill = Character::new('_', Style::default());
+        let wide = Character::new('漢', Style::new(Color::Red, Color::None, Modifiers::default()));
+        let mut c = Canvas::new_filled(4, 1, fill.clone());
+
+        c.draw_character((1, 0), &wide, BlendMode::Overwrite);
+        assert_eq!(
+            c.to_buffer(),
+            vec![vec![fill.clone(), wide.clone(), Character::new_continuation(*wide.style()), fill.clone()]]
+        );
+
+        c.draw_character((3, 0), &wide, BlendMode::Overwrite);
+        assert_eq!(
+            c.to_buffer(),
+            vec![vec![fill, wide.clone(), Character::new_continuation(*wide.style()), Character::new(' ', *wide.style())]]
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_over_wide() {
+        let fill = Character::new('_', Style::default());
+        let wide = Character::new('漢', Style::default());
+        let brush = Character::new('#', Style::default());
+        let space = Character::new(' ', Style::default());
+
+        let mut c = Canvas::new_filled(4, 1, fill.clone());
+        c.draw_character((1, 0), &wide, BlendMode::Overwrite);
+        c.draw_character((1, 0), &brush, BlendMode::Overwrite);
+        assert_eq!(c.to_buffer(), vec![vec![fill.clone(), brush.clone(), space.clone(), fill.clone()]]);
+
+        let mut c = Canvas::new_filled(4, 1, fill.clone());
+        c.draw_character((1, 0), &wide, BlendMode::Overwrite);
+        c.draw_character((2, 0), &brush, BlendMode::Overwrite);
+        assert_eq!(c.to_buffer(), vec![vec![fill.clone(), space.clone(), brush, fill.clone()]]);
+
+        let mut c = Canvas::new_filled(4, 1, fill.clone());
+        c.draw_character((2, 0), &wide, BlendMode::Overwrite);
+        c.draw_character((1, 0), &wide, BlendMode::Overwrite);
+        assert_eq!(c.to_buffer(), vec![vec![fill, wide, Character::new_continuation(Style::default()), space]]);
+    }
+
+    #[test]
+    fn test_canvas_draw_wide_brush_over_itself() {
+        let wide = Character::new('漢', Style::default());
+        let mut c = Canvas::new(5, 1);
+
+        for x in 0..5 {
+            c.draw_character((x, 0), &wide, BlendMode::Overwrite);
+        }
+        assert_eq!(
+            c.to_buffer(),
+            vec![vec![
+                wide.clone(), Character::new_continuation(Style::default()),
+                wide.clone(), Character::new_continuation(Style::default()),
+                Character::default()
+            ]]
+        );
+
+        c.draw_character((1, 0), &Character::new('字', Style::default()), BlendMode::Overwrite);
+        assert_eq!(c.row(0)[0], Character::default());
+        assert_eq!(c.row(0)[1], Character::new('字', Style::default()));
+    }
+
+    #[test]
+    fn test_canvas_draw_style_over_wide() {
+        let wide = Character::new('漢', Style::default());
+        let style = Style::new(Color::Red, Color::Black, Modifiers::default());
+        let mut c = Canvas::new(3, 1);
+
+        c.draw_character((0, 0), &wide, BlendMode::Overwrite);
+        c.draw_character((1, 0), &Character::new('#', style), BlendMode::OnlyStyle);
+        c.draw_character((0, 0), &Character::new_continuation(Style::default()), BlendMode::Overwrite);
+
+        assert_eq!(
+            c.to_buffer(),
+            vec![vec![Character::new('漢', style), Character::new_continuation(style), Character::default()]]
+        );
+    }
+
+    #[test]
+    fn test_canvas_row() {
+        let a = Character::new('a', Style::default());
+        let b = Character::new('b', Style::default());
+        let mut c = Canvas::new_with_buffer(vec![vec![a.clone(), a.clone()], vec![b.clone(), b.clone()]]);
+
+        assert_eq!(c.row(0), &[a.clone(), a.clone()]);
+        assert_eq!(c.row(1), &[b.clone(), b.clone()]);
+
+        c.row_mut(1)[0] = a.clone();
+        assert_eq!(c.to_buffer(), vec![vec![a.clone(), a.clone()], vec![a, b]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_canvas_row_out_of_bounds() {
+        Canvas::new(3, 2).row(2);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_canvas_buffer() {
+        let c = Canvas::new_filled(2, 1, Character::new('a', Style::default()));
+
+        assert_eq!(c.buffer(), c.to_buffer());
+        assert_eq!(c.buffer()[0], c.row(0));
+    }
+
+    #[test]
+    fn test_canvas_rows() {
+        let buffer: Vec<Vec<_>> = ["ab", "cd", "ef"].iter()
+            .map(|l| l.chars().map(|c| Character::new(c, Style::default())).collect())
+            .collect();
+        let c = Canvas::new_with_buffer(buffer.clone());
+
+        assert_eq!(c.rows().collect::<Vec<_>>(), buffer.iter().map(|r| r.as_slice()).collect::<Vec<_>>());
+        assert_eq!(c.cells(), buffer.concat().as_slice());
+        assert_eq!(Canvas::new(0, 2).rows().count(), 2);
+    }
+
+    #[test]
+    fn test_canvas_enumerate_cells() {
+        let c = Canvas::new_with_buffer(vec![
+            vec![Character::new('a', Style::default()), Character::new('b', Style::default())],
+            vec![Character::new('c', Style::default()), Character::new('d', Style::default())]
+        ]);
+
+        assert_eq!(
+            c.enumerate_cells().map(|(p, c)| (p, c.character())).collect::<Vec<_>>(),
+            vec![((0, 0), 'a'), ((1, 0), 'b'), ((0, 1), 'c'), ((1, 1), 'd')]
+        );
+        assert_eq!(Canvas::new(0, 3).enumerate_cells().count(), 0);
+    }
+
+    #[test]
+    fn test_canvas_iter_region() {
+        let c = Canvas::new(4, 3);
+
+        assert_eq!(
+            c.iter_region(Rect::new(2, 1, 5, 5)).map(|(p, _)| p).collect::<Vec<_>>(),
+            vec![(2, 1), (3, 1), (2, 2), (3, 2)]
+        );
+        assert_eq!(c.iter_region(Rect::new(4, 0, 2, 2)).count(), 0);
+    }
+
+    #[test]
+    fn test_canvas_for_each_cell() {
+        let style = Style::new(Color::Red, Color::None, Modifiers::default());
+        let mut c = Canvas::new(3, 2);
+
+        c.for_each_cell(|(x, y), c| if x == y {
+            *c = Character::new('\\', style);
+        });
+        c.cells_mut()[5] = Character::new('#', style);
+
+        assert_eq!(
+            c.enumerate_cells().filter(|(_, c)| *c.style() == style).map(|(p, c)| (p, c.character())).collect::<Vec<_>>(),
+            vec![((0, 0), '\\'), ((1, 1), '\\'), ((2, 1), '#')]
+        );
+    }
+
+    #[test]
+    fn test_canvas_map_cells() {
+        let c = Canvas::new_filled(2, 2, Character::new('a', Style::default()));
+
+        let mapped = c.map_cells(|(x, y), c| Character::new(if x == y { 'b' } else { c.character() }, Style::default()));
+
+        assert_eq!((mapped.width(), mapped.height()), (2, 2));
+        assert_eq!(mapped.cells().iter().map(|c| c.character()).collect::<String>(), "baab");
+        assert_eq!(c.cells().iter().map(|c| c.character()).collect::<String>(), "aaaa");
+    }
+
+    #[test]
+    fn test_canvas_draw_does_not_wrap_rows() {
+        let brush = Character::new('#', Style::default());
+        let mut c = Canvas::new(2, 2);
+
+        c.draw_character((2, 0), &brush, BlendMode::Overwrite);
+
+        assert_eq!(c.to_buffer(), Canvas::new(2, 2).to_buffer());
+    }
+
+    #[test]
+    fn test_canvas_resize_keeps_rows() {
+        let buffer: Vec<Vec<_>> = ["abc", "def"].iter()
+            .map(|l| l.chars().map(|c| Character::new(c, Style::default())).collect())
+            .collect();
+        let default = Character::default();
+
+        let mut c = Canvas::new_with_buffer(buffer.clone());
+        c.resize(2, 3);
+        assert_eq!(
+            c.to_buffer(),
+            vec![
+                buffer[0][..2].to_vec(),
+                buffer[1][..2].to_vec(),
+                vec![default.clone(), default.clone()]
+            ]
+        );
+
+        let mut c = Canvas::new_with_buffer(buffer.clone());
+        c.resize(4, 1);
+        assert_eq!(
+            c.to_buffer(),
+            vec![[buffer[0].clone(), vec![default]].concat()]
+        );
+
+        let mut c = Canvas::new_with_buffer(buffer.clone());
+        c.resize(3, 1);
+        c.resize(3, 2);
+        assert_eq!(c.row(0), buffer[0].as_slice());
+        assert_eq!(c.row(1), Canvas::new(3, 1).row(0));
+    }
+
+    #[test]
+    fn test_canvas_resize_cuts_wide() {
+        let mut c = Canvas::new(3, 1);
+        c.draw_character((1, 0), &Character::new('漢', Style::default()), BlendMode::Overwrite);
+
+        c.resize(2, 1);
+
+        assert_eq!(c.to_buffer(), vec![vec![Character::default(), Character::default()]]);
+    }
+
     #[test]
     fn test_canvas_resize_bigger() {
         let fill = Character::new('_', Style::new(Color::Red, Color::Black, Modifiers::new(false, false, false)));
         let default = Character::default();
-        let mut c = Canvas::new_filled(3, 2, fill);
+        let mut c = Canvas::new_filled(3, 2, fill.clone());
 
         c.resize(5, 3);
 
         assert_eq!(
-            c.buffer,
+            c.to_buffer(),
             vec![
                 vec![
-                    fill,
-                    fill,
-                    fill,
-                    default,
-                    default
+                    fill.clone(),
+                    fill.clone(),
+                    fill.clone(),
+                    default.clone(),
+                    default.clone()
                 ],
                 vec![
+                    fill.clone(),
+                    fill.clone(),
                     fill,
-                    fill,
-                    fill,
-                    default,
-                    default
+                    default.clone(),
+                    default.clone()
                 ],
                 vec![
-                    default,
-                    default,
-                    default,
-                    default,
+                    default.clone(),
+                    default.clone(),
+                    default.clone(),
+                    default.clone(),
                     default
                 ]
             ]
@@ -327,15 +843,15 @@ mod test {
     #[test]
     fn test_canvas_resize_smaller() {
         let fill = Character::new('_', Style::new(Color::Red, Color::Black, Modifiers::new(false, false, false)));
-        let mut c = Canvas::new_filled(3, 2, fill);
+        let mut c = Canvas::new_filled(3, 2, fill.clone());
 
         c.resize(2, 1);
 
         assert_eq!(
-            c.buffer,
+            c.to_buffer(),
             vec![
                 vec![
-                    fill,
+                    fill.clone(),
                     fill
                 ]
             ]
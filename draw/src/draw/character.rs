@@ -1,52 +1,152 @@
 use serde::{Serialize, Deserialize};
-use crate::{draw::style::Style, traits::copy_over::CopyFrom};
+use crate::{draw::{box_drawing, grapheme::Grapheme, style::Style}, traits::copy_over::CopyFrom};
 
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
 pub enum BlendMode {
     Overwrite,
     OnlyCharacter,
-    OnlyStyle
+    OnlyStyle,
+    MergeLines
 }
 
 #[derive(
-    Debug, Clone, Copy,
+    Debug, Clone,
     PartialEq, Eq,
     Serialize, Deserialize
 )]
 pub struct Character {
-    character: char,
+    character: Grapheme,
+    character_transparent: bool,
+    continuation: bool,
     style: Style
 }
 
 impl Character {
-    pub fn new(character: char, style: Style) -> Self {
+    pub fn new(character: impl Into<Grapheme>, style: Style) -> Self {
+        Self {
+            character: character.into(),
+            character_transparent: false,
+            continuation: false,
+            style
+        }
+    }
+
+    pub fn new_transparent(style: Style) -> Self {
+        Self {
+            character: Grapheme::from(' '),
+            character_transparent: true,
+            continuation: false,
+            style
+        }
+    }
+
+    pub fn transparent() -> Self {
+        Self::new_transparent(Style::transparent())
+    }
+
+    // Fills the right half of a double-width glyph drawn in the cell to its left.
+    pub fn new_continuation(style: Style) -> Self {
         Self {
-            character,
+            character: Grapheme::from(' '),
+            character_transparent: false,
+            continuation: true,
             style
         }
     }
 
     pub fn blend(&mut self, rhs: &Self, mode: BlendMode) {
+        if rhs.is_transparent() {
+            return;
+        }
+
         match mode {
             BlendMode::Overwrite => {
-                self.copy_from(rhs)
+                self.blend_character(rhs);
+                self.style.blend(&rhs.style)
             },
             BlendMode::OnlyCharacter => {
-                self.character = rhs.character
+                self.blend_character(rhs)
             },
             BlendMode::OnlyStyle => {
-                self.style.copy_from(&rhs.style)
+                self.style.blend(&rhs.style)
+            },
+            BlendMode::MergeLines => {
+                if !rhs.character_transparent {
+                    self.character = box_drawing::merge(self.character(), rhs.character())
+                        .map_or_else(|| rhs.character.clone(), Grapheme::from);
+                    self.character_transparent = false;
+                    self.continuation = false;
+                }
+                self.style.blend(&rhs.style)
             }
         }
     }
 
+    fn blend_character(&mut self, rhs: &Self) {
+        if !rhs.character_transparent {
+            self.character = rhs.character.clone();
+            self.character_transparent = false;
+            self.continuation = false;
+        }
+    }
+
 
     pub fn character(&self) -> char {
-        self.character
+        self.character.base()
+    }
+
+    pub fn grapheme(&self) -> &str {
+        self.character.as_str()
+    }
+
+    // Keeps any combining marks when the base character stays the same.
+    pub(crate) fn set_character(&mut self, character: char) {
+        if character != self.character() {
+            self.character = Grapheme::from(character);
+        }
+    }
+
+    pub(crate) fn set_continuation(&mut self, continuation: bool) {
+        self.continuation = continuation;
+    }
+
+    pub fn is_continuation(&self) -> bool {
+        self.continuation
+    }
+
+    pub fn width(&self) -> usize {
+        if self.continuation {
+            0
+        } else if self.character_transparent {
+            1
+        } else {
+            self.character.width()
+        }
+    }
+
+    pub fn is_wide(&self) -> bool {
+        self.width() == 2
+    }
+
+    pub fn is_character_transparent(&self) -> bool {
+        self.character_transparent
+    }
+
+    pub fn is_transparent(&self) -> bool {
+        self.character_transparent && self.style.fg.is_transparent() && self.style.bg.is_transparent()
     }
 
     pub fn style(&self) -> &Style {
         &self.style
     }
+
+    pub(crate) fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
 }
 
 impl Default for Character {
@@ -60,7 +160,9 @@ impl Default for Character {
 
 impl CopyFrom for Character {
     fn copy_from(&mut self, rhs: &Self) {
-        self.character= rhs.character;
+        self.character = rhs.character.clone();
+        self.character_transparent = rhs.character_transparent;
+        self.continuation = rhs.continuation;
         self.style.copy_from(&rhs.style);
     }
 }
@@ -76,7 +178,7 @@ mod tests {
     fn test_character_new() {
         assert_eq!(
             Character::new('a', Style::new(Color::Green, Color::Yellow, Modifiers::new(true, false, false))),
-            Character { character: 'a', style: Style::new(Color::Green, Color::Yellow, Modifiers::new(true, false, false)) }
+            Character { character: Grapheme::from('a'), character_transparent: false, continuation: false, style: Style::new(Color::Green, Color::Yellow, Modifiers::new(true, false, false)) }
         );
     }
 
@@ -84,10 +186,68 @@ mod tests {
     fn test_character_default() {
         assert_eq!(
             Character::default(),
-            Character { character: ' ', style: Style::default() }
+            Character { character: Grapheme::from(' '), character_transparent: false, continuation: false, style: Style::default() }
         )
     }
 
+    #[test]
+    fn test_character_transparent() {
+        assert_eq!(
+            Character::transparent(),
+            Character { character: Grapheme::from(' '), character_transparent: true, continuation: false, style: Style::transparent() }
+        );
+        assert!(Character::transparent().is_transparent());
+        assert!(!Character::new_transparent(Style::default()).is_transparent());
+        assert!(Character::new_transparent(Style::default()).is_character_transparent());
+        assert!(!Character::default().is_character_transparent());
+    }
+
+    #[test]
+    fn test_character_width() {
+        assert_eq!(Character::new('a', Style::default()).width(), 1);
+        assert_eq!(Character::new('─', Style::default()).width(), 1);
+        assert_eq!(Character::new('漢', Style::default()).width(), 2);
+        assert_eq!(Character::new('😀', Style::default()).width(), 2);
+        assert_eq!(Character::new('\u{301}', Style::default()).width(), 1);
+        assert_eq!(Character::new_continuation(Style::default()).width(), 0);
+        assert_eq!(Character::transparent().width(), 1);
+        assert!(Character::new('漢', Style::default()).is_wide());
+        assert!(!Character::new('a', Style::default()).is_wide());
+    }
+
+    #[test]
+    fn test_character_grapheme() {
+        let c = Character::new("e\u{301}", Style::default());
+
+        assert_eq!(c.grapheme(), "e\u{301}");
+        assert_eq!(c.character(), 'e');
+        assert_eq!(c.width(), 1);
+        assert!(Character::new("👍\u{1F3FD}", Style::default()).is_wide());
+    }
+
+    #[test]
+    fn test_character_set_character() {
+        let mut c = Character::new("e\u{301}", Style::default());
+
+        c.set_character('e');
+        assert_eq!(c.grapheme(), "e\u{301}");
+
+        c.set_character('a');
+        assert_eq!(c.grapheme(), "a");
+    }
+
+    #[test]
+    fn test_character_blend_onto_continuation() {
+        let mut c1 = Character::new_continuation(Style::default());
+
+        c1.blend(&Character::new_transparent(Style::new(Color::Red, Color::Transparent, Modifiers::default())), BlendMode::Overwrite);
+        assert!(c1.is_continuation());
+
+        c1.blend(&Character::new('a', Style::default()), BlendMode::OnlyCharacter);
+        assert!(!c1.is_continuation());
+        assert_eq!(c1.character(), 'a');
+    }
+
     #[test]
     fn test_character_blend_overwrite() {
         let mut c1 = Character::new('a', Style::new(Color::Yellow, Color::Black, Modifiers::new(false, true, false)));
@@ -123,4 +283,71 @@ mod tests {
             Character::new('a', Style::new(Color::Green, Color::White, Modifiers::new(true, false, false)))
         );
     }
+
+    #[test]
+    fn test_character_blend_transparent() {
+        let mut c1 = Character::new('a', Style::new(Color::Yellow, Color::Black, Modifiers::new(false, true, false)));
+        let before = c1.clone();
+
+        c1.blend(&Character::transparent(), BlendMode::Overwrite);
+        c1.blend(&Character::transparent(), BlendMode::OnlyStyle);
+        c1.blend(&Character::transparent(), BlendMode::MergeLines);
+
+        assert_eq!(c1, before);
+    }
+
+    #[test]
+    fn test_character_blend_transparent_channels() {
+        let mut c1 = Character::new('a', Style::new(Color::Yellow, Color::Black, Modifiers::new(false, true, false)));
+
+        c1.blend(&Character::new_transparent(Style::new(Color::Transparent, Color::Red, Modifiers::default())), BlendMode::Overwrite);
+        assert_eq!(
+            c1,
+            Character::new('a', Style::new(Color::Yellow, Color::Red, Modifiers::default()))
+        );
+
+        c1.blend(&Character::new('b', Style::new(Color::Green, Color::Transparent, Modifiers::default())), BlendMode::Overwrite);
+        assert_eq!(
+            c1,
+            Character::new('b', Style::new(Color::Green, Color::Red, Modifiers::default()))
+        );
+    }
+
+    #[test]
+    fn test_character_blend_onto_transparent() {
+        let mut c1 = Character::transparent();
+        let c2 = Character::new('b', Style::new(Color::Green, Color::White, Modifiers::new(true, false, false)));
+
+        c1.blend(&c2, BlendMode::OnlyCharacter);
+        assert_eq!(
+            c1,
+            Character::new('b', Style::transparent())
+        );
+
+        c1.blend(&c2, BlendMode::Overwrite);
+        assert_eq!(c1, c2);
+    }
+
+    #[test]
+    fn test_character_blend_merge_lines() {
+        let mut c1 = Character::new('│', Style::new(Color::Yellow, Color::Black, Modifiers::new(false, true, false)));
+        let c2 = Character::new('═', Style::new(Color::Green, Color::White, Modifiers::new(true, false, false)));
+
+        c1.blend(&c2, BlendMode::MergeLines);
+
+        assert_eq!(
+            c1,
+            Character::new('╪', Style::new(Color::Green, Color::White, Modifiers::new(true, false, false)))
+        );
+    }
+
+    #[test]
+    fn test_character_blend_merge_lines_not_box_drawing() {
+        let mut c1 = Character::new('a', Style::new(Color::Yellow, Color::Black, Modifiers::new(false, true, false)));
+        let c2 = Character::new('─', Style::new(Color::Green, Color::White, Modifiers::new(true, false, false)));
+
+        c1.blend(&c2, BlendMode::MergeLines);
+
+        assert_eq!(c1, c2);
+    }
 }
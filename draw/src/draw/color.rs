@@ -0,0 +1,327 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::style::Color;
+
+const NAMES: [&str; 16] = [
+    "black", "dark_red", "dark_green", "dark_yellow", "dark_blue", "dark_magenta", "dark_cyan", "light_gray",
+    "dark_gray", "red", "green", "yellow", "blue", "magenta", "cyan", "white"
+];
+
+// RGB values the 16 named colours are shown as, in xterm order.
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub struct ColorTheme {
+    pub colors: [[u8; 3]; 16]
+}
+
+impl ColorTheme {
+    pub fn new(colors: [[u8; 3]; 16]) -> Self {
+        Self {
+            colors
+        }
+    }
+
+    pub fn xterm() -> Self {
+        Self::new([
+            [0, 0, 0], [128, 0, 0], [0, 128, 0], [128, 128, 0],
+            [0, 0, 128], [128, 0, 128], [0, 128, 128], [192, 192, 192],
+            [128, 128, 128], [255, 0, 0], [0, 255, 0], [255, 255, 0],
+            [0, 0, 255], [255, 0, 255], [0, 255, 255], [255, 255, 255]
+        ])
+    }
+}
+
+impl Default for ColorTheme {
+    fn default() -> Self {
+        Self::xterm()
+    }
+}
+
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq
+)]
+pub struct ParseColorError {
+    input: String
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid color `{}`", self.input)
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+
+fn hue_to_rgb(h: f32, chroma: f32, min: f32) -> Color {
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x)
+    };
+    let channel = |v: f32| ((v + min) * 255.0).round().clamp(0.0, 255.0) as u8;
+    Color::Rgb { r: channel(r), g: channel(g), b: channel(b) }
+}
+
+impl Color {
+    // Named colours in xterm order, so `NAMED[i]` matches `Indexed { i }` below 16.
+    pub const NAMED: [Color; 16] = [
+        Color::Black, Color::DarkRed, Color::DarkGreen, Color::DarkYellow,
+        Color::DarkBlue, Color::DarkMagenta, Color::DarkCyan, Color::LightGray,
+        Color::DarkGray, Color::Red, Color::Green, Color::Yellow,
+        Color::Blue, Color::Magenta, Color::Cyan, Color::White
+    ];
+
+    pub fn named_index(&self) -> Option<u8> {
+        Self::NAMED.iter().position(|c| c == self).map(|i| i as u8)
+    }
+
+    pub fn to_rgb(&self) -> Option<[u8; 3]> {
+        self.to_rgb_with(&ColorTheme::default())
+    }
+
+    pub fn to_rgb_with(&self, theme: &ColorTheme) -> Option<[u8; 3]> {
+        match *self {
+            Color::None | Color::Transparent => None,
+            Color::Rgb { r, g, b } => Some([r, g, b]),
+            Color::Indexed { i } if i < 16 => Some(theme.colors[i as usize]),
+            Color::Indexed { i } if i < 232 => {
+                let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+                let i = i - 16;
+                Some([level(i / 36), level(i / 6 % 6), level(i % 6)])
+            },
+            Color::Indexed { i } => Some([8 + (i - 232) * 10; 3]),
+            c => c.named_index().map(|i| theme.colors[i as usize])
+        }
+    }
+
+    // Hue in degrees, saturation and lightness from 0 to 1.
+    pub fn to_hsl(&self) -> Option<(f32, f32, f32)> {
+        let (h, max, min) = self.hue()?;
+        let l = (max + min) / 2.0;
+        let s = if max == min { 0.0 } else { (max - min) / (1.0 - (2.0 * l - 1.0).abs()) };
+        Some((h, s, l))
+    }
+
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Self {
+        let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        hue_to_rgb(h, chroma, l - chroma / 2.0)
+    }
+
+    // Hue in degrees, saturation and value from 0 to 1.
+    pub fn to_hsv(&self) -> Option<(f32, f32, f32)> {
+        let (h, max, min) = self.hue()?;
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+        Some((h, s, max))
+    }
+
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> Self {
+        let (s, v) = (s.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+        let chroma = v * s;
+        hue_to_rgb(h, chroma, v - chroma)
+    }
+
+    fn hue(&self) -> Option<(f32, f32, f32)> {
+        let [r, g, b] = self.to_rgb()?.map(|v| v as f32 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let h = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        Some((h, max, min))
+    }
+
+    pub fn distance(&self, other: &Color) -> Option<u32> {
+        let (a, b) = (self.to_rgb()?, other.to_rgb()?);
+        Some(a.iter().zip(b).map(|(a, b)| (*a as i32 - b as i32).pow(2) as u32).sum())
+    }
+
+    // Earlier colours win ties, colours without RGB values are never picked.
+    pub fn nearest(&self, palette: &[Color]) -> Option<Color> {
+        palette.iter()
+            .filter_map(|c| self.distance(c).map(|d| (d, *c)))
+            .min_by_key(|(d, _)| *d)
+            .map(|(_, c)| c)
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Color::None => write!(f, "none"),
+            Color::Transparent => write!(f, "transparent"),
+            Color::Indexed { i } => write!(f, "indexed:{}", i),
+            Color::Rgb { r, g, b } => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+            c => write!(f, "{}", NAMES[c.named_index().unwrap_or_default() as usize])
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseColorError { input: s.to_string() };
+        let input = s.trim().to_ascii_lowercase();
+
+        if let Some(hex) = input.strip_prefix('#') {
+            let digits: Vec<u8> = hex.chars().map(|c| c.to_digit(16).map(|d| d as u8)).collect::<Option<_>>().ok_or_else(error)?;
+            let [r, g, b] = match digits[..] {
+                [r, g, b] => [r * 17, g * 17, b * 17],
+                [r1, r2, g1, g2, b1, b2] => [r1 << 4 | r2, g1 << 4 | g2, b1 << 4 | b2],
+                _ => return Err(error())
+            };
+            return Ok(Color::Rgb { r, g, b });
+        }
+
+        if let Some(args) = input.strip_prefix("rgb(").and_then(|s| s.strip_suffix(')')) {
+            let channels: Vec<u8> = args.split(',').map(|c| c.trim().parse().ok()).collect::<Option<_>>().ok_or_else(error)?;
+            let [r, g, b] = channels[..] else {
+                return Err(error());
+            };
+            return Ok(Color::Rgb { r, g, b });
+        }
+
+        if let Some(i) = input.strip_prefix("indexed:") {
+            return i.trim().parse().map(|i| Color::Indexed { i }).map_err(|_| error());
+        }
+
+        let name = input.replace(['-', ' '], "_").replace("grey", "gray");
+        match name.as_str() {
+            "none" => Ok(Color::None),
+            "transparent" => Ok(Color::Transparent),
+            _ => NAMES.iter()
+                .position(|n| *n == name || n.replace('_', "") == name)
+                .map(|i| Self::NAMED[i])
+                .ok_or_else(error)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_parse_hex() {
+        assert_eq!("#ff8000".parse(), Ok(Color::Rgb { r: 255, g: 128, b: 0 }));
+        assert_eq!("#F80".parse(), Ok(Color::Rgb { r: 255, g: 136, b: 0 }));
+        assert!("#ff80".parse::<Color>().is_err());
+        assert!("#gg0000".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn test_color_parse_rgb() {
+        assert_eq!("rgb(1, 2,3)".parse(), Ok(Color::Rgb { r: 1, g: 2, b: 3 }));
+        assert!("rgb(1, 2)".parse::<Color>().is_err());
+        assert!("rgb(1, 2, 256)".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn test_color_parse_names() {
+        assert_eq!("red".parse(), Ok(Color::Red));
+        assert_eq!("Dark-Red".parse(), Ok(Color::DarkRed));
+        assert_eq!("darkred".parse(), Ok(Color::DarkRed));
+        assert_eq!("light grey".parse(), Ok(Color::LightGray));
+        assert_eq!("none".parse(), Ok(Color::None));
+        assert_eq!("transparent".parse(), Ok(Color::Transparent));
+        assert_eq!("indexed:42".parse(), Ok(Color::Indexed { i: 42 }));
+        assert!("indexed:256".parse::<Color>().is_err());
+        assert_eq!(
+            "purple".parse::<Color>().unwrap_err().to_string(),
+            "Invalid color `purple`"
+        );
+    }
+
+    #[test]
+    fn test_color_display_round_trip() {
+        let mut colors = Color::NAMED.to_vec();
+        colors.extend([Color::None, Color::Transparent, Color::Indexed { i: 7 }, Color::Rgb { r: 1, g: 170, b: 255 }]);
+
+        for c in colors {
+            assert_eq!(c.to_string().parse(), Ok(c));
+        }
+        assert_eq!(Color::DarkGray.to_string(), "dark_gray");
+        assert_eq!(Color::Rgb { r: 1, g: 170, b: 255 }.to_string(), "#01aaff");
+    }
+
+    #[test]
+    fn test_color_to_rgb() {
+        assert_eq!(Color::None.to_rgb(), None);
+        assert_eq!(Color::Transparent.to_rgb(), None);
+        assert_eq!(Color::DarkRed.to_rgb(), Some([128, 0, 0]));
+        assert_eq!(Color::Indexed { i: 1 }.to_rgb(), Some([128, 0, 0]));
+        assert_eq!(Color::Indexed { i: 16 }.to_rgb(), Some([0, 0, 0]));
+        assert_eq!(Color::Indexed { i: 208 }.to_rgb(), Some([255, 135, 0]));
+        assert_eq!(Color::Indexed { i: 231 }.to_rgb(), Some([255, 255, 255]));
+        assert_eq!(Color::Indexed { i: 232 }.to_rgb(), Some([8, 8, 8]));
+        assert_eq!(Color::Indexed { i: 255 }.to_rgb(), Some([238, 238, 238]));
+    }
+
+    #[test]
+    fn test_color_to_rgb_with_theme() {
+        let mut theme = ColorTheme::xterm();
+        theme.colors[9] = [250, 80, 80];
+
+        assert_eq!(Color::Red.to_rgb_with(&theme), Some([250, 80, 80]));
+        assert_eq!(Color::Indexed { i: 9 }.to_rgb_with(&theme), Some([250, 80, 80]));
+        assert_eq!(Color::Indexed { i: 196 }.to_rgb_with(&theme), Some([255, 0, 0]));
+    }
+
+    #[test]
+    fn test_color_hsl() {
+        assert_eq!(Color::Rgb { r: 255, g: 0, b: 0 }.to_hsl(), Some((0.0, 1.0, 0.5)));
+        assert_eq!(Color::Rgb { r: 0, g: 0, b: 255 }.to_hsl(), Some((240.0, 1.0, 0.5)));
+        assert_eq!(Color::White.to_hsl(), Some((0.0, 0.0, 1.0)));
+        assert_eq!(Color::None.to_hsl(), None);
+
+        assert_eq!(Color::from_hsl(120.0, 1.0, 0.25), Color::Rgb { r: 0, g: 128, b: 0 });
+        assert_eq!(Color::from_hsl(-60.0, 1.0, 0.5), Color::Rgb { r: 255, g: 0, b: 255 });
+
+        let c = Color::Rgb { r: 12, g: 200, b: 99 };
+        let (h, s, l) = c.to_hsl().unwrap();
+        assert_eq!(Color::from_hsl(h, s, l), c);
+    }
+
+    #[test]
+    fn test_color_hsv() {
+        assert_eq!(Color::Rgb { r: 0, g: 255, b: 0 }.to_hsv(), Some((120.0, 1.0, 1.0)));
+        assert_eq!(Color::Black.to_hsv(), Some((0.0, 0.0, 0.0)));
+        assert_eq!(Color::from_hsv(60.0, 1.0, 1.0), Color::Rgb { r: 255, g: 255, b: 0 });
+
+        let c = Color::Rgb { r: 180, g: 30, b: 77 };
+        let (h, s, v) = c.to_hsv().unwrap();
+        assert_eq!(Color::from_hsv(h, s, v), c);
+    }
+
+    #[test]
+    fn test_color_nearest() {
+        let palette = [Color::None, Color::Black, Color::White, Color::Indexed { i: 196 }];
+
+        assert_eq!(Color::Rgb { r: 240, g: 20, b: 20 }.nearest(&palette), Some(Color::Indexed { i: 196 }));
+        assert_eq!(Color::DarkBlue.nearest(&palette), Some(Color::Black));
+        assert_eq!(Color::None.nearest(&palette), None);
+        assert_eq!(Color::Red.nearest(&[]), None);
+        assert_eq!(Color::Black.distance(&Color::White), Some(3 * 255 * 255));
+    }
+}
@@ -0,0 +1,239 @@
+use serde::{Serialize, Deserialize};
+
+use super::{canvas::Canvas, character::Character};
+
+// Consecutive changed cells of one row.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq,
+    Serialize, Deserialize
+)]
+struct DiffRun {
+    x: usize,
+    y: usize,
+    old: Vec<Character>,
+    new: Vec<Character>
+}
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq,
+    Serialize, Deserialize
+)]
+pub struct CanvasDiff {
+    old_size: (usize, usize),
+    new_size: (usize, usize),
+    runs: Vec<DiffRun>
+}
+
+// Cells outside of a canvas count as default, which is what `resize` fills them with.
+pub fn diff(old: &Canvas, new: &Canvas) -> CanvasDiff {
+    let default = Character::default();
+    let mut runs: Vec<DiffRun> = Vec::new();
+    for y in 0..old.height().max(new.height()) {
+        for x in 0..old.width().max(new.width()) {
+            let o = old.get(x, y).unwrap_or(&default);
+            let n = new.get(x, y).unwrap_or(&default);
+            if o == n {
+                continue;
+            }
+
+            match runs.last_mut() {
+                Some(r) if r.y == y && r.x + r.old.len() == x => {
+                    r.old.push(o.clone());
+                    r.new.push(n.clone());
+                },
+                _ => runs.push(DiffRun { x, y, old: vec![o.clone()], new: vec![n.clone()] })
+            }
+        }
+    }
+
+    CanvasDiff {
+        old_size: (old.width(), old.height()),
+        new_size: (new.width(), new.height()),
+        runs
+    }
+}
+
+impl CanvasDiff {
+    pub fn old_size(&self) -> (usize, usize) {
+        self.old_size
+    }
+
+    pub fn new_size(&self) -> (usize, usize) {
+        self.new_size
+    }
+
+    pub fn columns_added(&self) -> usize {
+        self.new_size.0.saturating_sub(self.old_size.0)
+    }
+
+    pub fn columns_removed(&self) -> usize {
+        self.old_size.0.saturating_sub(self.new_size.0)
+    }
+
+    pub fn rows_added(&self) -> usize {
+        self.new_size.1.saturating_sub(self.old_size.1)
+    }
+
+    pub fn rows_removed(&self) -> usize {
+        self.old_size.1.saturating_sub(self.new_size.1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.old_size == self.new_size && self.runs.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.runs.iter().map(|r| r.old.len()).sum()
+    }
+
+    // Moves a diff of a cropped region back to where the region sits on a canvas of `size`.
+    pub(crate) fn offset(mut self, origin: (usize, usize), size: (usize, usize)) -> Self {
+        for r in self.runs.iter_mut() {
+            r.x += origin.0;
+            r.y += origin.1;
+        }
+        self.old_size = size;
+        self.new_size = size;
+        self
+    }
+
+    pub fn changes(&self) -> impl Iterator<Item = ((usize, usize), &Character, &Character)> + '_ {
+        self.runs.iter().flat_map(|r| {
+            r.old.iter().zip(&r.new).enumerate().map(move |(i, (o, n))| ((r.x + i, r.y), o, n))
+        })
+    }
+
+    pub fn inverse(&self) -> CanvasDiff {
+        CanvasDiff {
+            old_size: self.new_size,
+            new_size: self.old_size,
+            runs: self.runs.iter().map(|r| DiffRun {
+                x: r.x,
+                y: r.y,
+                old: r.new.clone(),
+                new: r.old.clone()
+            }).collect()
+        }
+    }
+
+    pub fn apply(&self, canvas: &mut Canvas) {
+        canvas.resize(self.new_size.0, self.new_size.1);
+        for ((x, y), _, new) in self.changes() {
+            if let Some(c) = canvas.get_mut(x, y) {
+                *c = new.clone();
+            }
+        }
+    }
+
+    pub fn revert(&self, canvas: &mut Canvas) {
+        canvas.resize(self.old_size.0, self.old_size.1);
+        for ((x, y), old, _) in self.changes() {
+            if let Some(c) = canvas.get_mut(x, y) {
+                *c = old.clone();
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{character::BlendMode, style::{Style, Color, Modifiers}, test_helpers::{render, canvas}};
+
+    use super::*;
+
+    #[test]
+    fn test_diff_identical() {
+        let d = diff(&canvas("ab\ncd"), &canvas("ab\ncd"));
+
+        assert!(d.is_empty());
+        assert_eq!(d.changes().count(), 0);
+    }
+
+    #[test]
+    fn test_diff_changes() {
+        let d = diff(&canvas("abcd\nefgh"), &canvas("aXYd\nefgZ"));
+
+        assert_eq!(
+            d.changes().map(|(p, o, n)| (p, o.character(), n.character())).collect::<Vec<_>>(),
+            vec![((1, 0), 'b', 'X'), ((2, 0), 'c', 'Y'), ((3, 1), 'h', 'Z')]
+        );
+        assert_eq!(d.runs.len(), 2);
+        assert_eq!(d.len(), 3);
+        assert!(!d.is_empty());
+    }
+
+    #[test]
+    fn test_diff_style_only() {
+        let old = canvas("a");
+        let mut new = canvas("a");
+        new.draw_character((0, 0), &Character::new_transparent(Style::transparent()), BlendMode::Overwrite);
+        assert!(diff(&old, &new).is_empty());
+
+        new.draw_character((0, 0), &Character::new('a', Style::new(Color::Red, Color::None, Modifiers::default())), BlendMode::Overwrite);
+        assert_eq!(diff(&old, &new).changes().count(), 1);
+    }
+
+    #[test]
+    fn test_diff_sizes() {
+        let d = diff(&canvas("ab\ncd"), &canvas("abc"));
+
+        assert_eq!(d.old_size(), (2, 2));
+        assert_eq!(d.new_size(), (3, 1));
+        assert_eq!(d.columns_added(), 1);
+        assert_eq!(d.columns_removed(), 0);
+        assert_eq!(d.rows_added(), 0);
+        assert_eq!(d.rows_removed(), 1);
+        assert_eq!(
+            d.changes().map(|(p, o, n)| (p, o.character(), n.character())).collect::<Vec<_>>(),
+            vec![((2, 0), ' ', 'c'), ((0, 1), 'c', ' '), ((1, 1), 'd', ' ')]
+        );
+    }
+
+    #[test]
+    fn test_diff_apply_revert() {
+        let old = canvas("ab\ncd\nef");
+        let new = canvas("aXZ\ncdQ");
+        let d = diff(&old, &new);
+
+        let mut c = canvas("ab\ncd\nef");
+        d.apply(&mut c);
+        assert_eq!(render(&c), render(&new));
+        assert_eq!((c.width(), c.height()), (3, 2));
+
+        d.revert(&mut c);
+        assert_eq!(render(&c), render(&old));
+        assert_eq!((c.width(), c.height()), (2, 3));
+
+        d.inverse().apply(&mut c);
+        assert_eq!(render(&c), render(&old));
+        d.inverse().revert(&mut c);
+        assert_eq!(render(&c), render(&new));
+    }
+
+    #[test]
+    fn test_diff_wide() {
+        let old = canvas("ab");
+        let new = canvas("漢");
+        let d = diff(&old, &new);
+
+        let mut c = canvas("ab");
+        d.apply(&mut c);
+        assert_eq!(c.to_buffer(), new.to_buffer());
+
+        d.revert(&mut c);
+        assert_eq!(c.to_buffer(), old.to_buffer());
+    }
+
+    #[test]
+    fn test_diff_serialize() {
+        let d = diff(&canvas("abcd\nefgh"), &canvas("aXYd"));
+
+        let bytes = bincode::serialize(&d).unwrap();
+        assert_eq!(bincode::deserialize::<CanvasDiff>(&bytes).unwrap(), d);
+    }
+}
@@ -0,0 +1,289 @@
+use super::{canvas::Canvas, character::{BlendMode, Character}, region::Transparency};
+
+#[derive(Debug)]
+pub struct Layer {
+    name: String,
+    canvas: Canvas,
+    pub visible: bool,
+    pub locked: bool,
+    pub blend_mode: BlendMode,
+    pub transparency: Transparency
+}
+
+impl Layer {
+    pub fn new(name: &str, canvas: Canvas) -> Self {
+        Self {
+            name: name.to_string(),
+            canvas,
+            visible: true,
+            locked: false,
+            blend_mode: BlendMode::Overwrite,
+            transparency: Transparency::DefaultTransparent
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn canvas(&self) -> &Canvas {
+        &self.canvas
+    }
+
+    pub fn canvas_mut(&mut self) -> Option<&mut Canvas> {
+        if self.locked {
+            None
+        } else {
+            Some(&mut self.canvas)
+        }
+    }
+}
+
+
+#[derive(Debug)]
+pub struct Document {
+    width: usize,
+    height: usize,
+    layers: Vec<Layer>
+}
+
+impl Document {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            layers: Vec::new()
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn layers(&self) -> &[Layer] {
+        self.layers.as_slice()
+    }
+
+    pub fn z_index(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|l| l.name == name)
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&Layer> {
+        self.layers.iter().find(|l| l.name == name)
+    }
+
+    pub fn layer_mut(&mut self, name: &str) -> Option<&mut Layer> {
+        self.layers.iter_mut().find(|l| l.name == name)
+    }
+
+    pub fn add_layer(&mut self, name: &str) -> Option<&mut Layer> {
+        let i = self.push_layer(Layer::new(name, Canvas::new_filled(self.width, self.height, Character::transparent()))).ok()?;
+        self.layers.get_mut(i)
+    }
+
+    // Hands the layer back when its name is taken, so it isn't lost.
+    pub fn push_layer(&mut self, mut layer: Layer) -> Result<usize, Layer> {
+        if self.z_index(&layer.name).is_some() {
+            return Err(layer);
+        }
+
+        layer.canvas.resize_filled(self.width, self.height, &Character::transparent());
+        self.layers.push(layer);
+        Ok(self.layers.len() - 1)
+    }
+
+    pub fn remove_layer(&mut self, name: &str) -> Option<Layer> {
+        let i = self.z_index(name)?;
+        Some(self.layers.remove(i))
+    }
+
+    pub fn rename_layer(&mut self, name: &str, new_name: &str) -> bool {
+        if self.z_index(new_name).is_some() {
+            return false;
+        }
+
+        match self.layer_mut(name) {
+            Some(l) => {
+                l.name = new_name.to_string();
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn move_layer(&mut self, name: &str, z_index: usize) -> bool {
+        match self.z_index(name) {
+            Some(i) => {
+                let layer = self.layers.remove(i);
+                let z_index = z_index.min(self.layers.len());
+                self.layers.insert(z_index, layer);
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+        for l in self.layers.iter_mut() {
+            l.canvas.resize_filled(width, height, &Character::transparent());
+        }
+    }
+
+    pub fn flatten(&self) -> Canvas {
+        let mut canvas = Canvas::new(self.width, self.height);
+        for l in self.layers.iter().filter(|l| l.visible) {
+            canvas.paste(&l.canvas, (0, 0), l.blend_mode, l.transparency);
+        }
+        canvas
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{style::{Style, Color, Modifiers}, test_helpers::{render, canvas}};
+
+    use super::*;
+
+    fn document() -> Document {
+        let mut d = Document::new(3, 2);
+        d.push_layer(Layer::new("background", canvas("...\n..."))).unwrap();
+        d.push_layer(Layer::new("lines", canvas("  |\n--+"))).unwrap();
+        d.push_layer(Layer::new("text", canvas("a  \n   "))).unwrap();
+        d
+    }
+
+    #[test]
+    fn test_document_flatten() {
+        assert_eq!(
+            render(&document().flatten()),
+            "a.|\n--+"
+        );
+    }
+
+    #[test]
+    fn test_document_flatten_hidden() {
+        let mut d = document();
+        d.layer_mut("lines").unwrap().visible = false;
+
+        assert_eq!(
+            render(&d.flatten()),
+            "a..\n..."
+        );
+    }
+
+    #[test]
+    fn test_document_flatten_opaque() {
+        let mut d = document();
+        d.layer_mut("text").unwrap().transparency = Transparency::Opaque;
+
+        assert_eq!(
+            render(&d.flatten()),
+            "a  \n   "
+        );
+    }
+
+    #[test]
+    fn test_document_flatten_blend_mode() {
+        let style = Style::new(Color::Red, Color::Black, Modifiers::default());
+        let mut d = document();
+        let colour = d.add_layer("colour").unwrap();
+        colour.blend_mode = BlendMode::OnlyStyle;
+        colour.canvas_mut().unwrap().draw_character((2, 1), &Character::new('x', style), BlendMode::Overwrite);
+
+        let flat = d.flatten();
+
+        assert_eq!(render(&flat), "a.|\n--+");
+        assert_eq!(flat.row(1)[2], Character::new('+', style));
+    }
+
+    #[test]
+    fn test_document_add_layer_is_transparent() {
+        let mut d = document();
+        d.add_layer("empty").unwrap().transparency = Transparency::Opaque;
+
+        assert!(d.layer("empty").unwrap().canvas().cells().iter().all(|c| c.is_transparent()));
+        assert_eq!(
+            render(&d.flatten()),
+            "a.|\n--+"
+        );
+    }
+
+    #[test]
+    fn test_document_move_layer() {
+        let mut d = document();
+
+        assert!(d.move_layer("background", 10));
+        assert_eq!(d.z_index("background"), Some(2));
+        assert_eq!(
+            render(&d.flatten()),
+            "...\n..."
+        );
+
+        assert!(d.move_layer("background", 0));
+        assert_eq!(d.z_index("background"), Some(0));
+        assert!(!d.move_layer("missing", 0));
+    }
+
+    #[test]
+    fn test_document_locked_layer() {
+        let mut d = document();
+        let layer = d.layer_mut("text").unwrap();
+
+        layer.locked = true;
+        assert!(layer.canvas_mut().is_none());
+
+        layer.locked = false;
+        assert!(layer.canvas_mut().is_some());
+    }
+
+    #[test]
+    fn test_document_layer_names_unique() {
+        let mut d = document();
+
+        assert!(d.add_layer("text").is_none());
+        let duplicate = d.push_layer(Layer::new("lines", canvas("x"))).unwrap_err();
+        assert_eq!(duplicate.name(), "lines");
+        assert_eq!(render(duplicate.canvas()), "x");
+        assert_eq!(d.push_layer(Layer::new("top", canvas("x"))).unwrap(), 3);
+        assert_eq!(d.remove_layer("top").unwrap().name(), "top");
+        assert!(!d.rename_layer("text", "lines"));
+        assert!(d.rename_layer("text", "caption"));
+        assert!(d.layer("text").is_none());
+        assert_eq!(d.z_index("caption"), Some(2));
+        assert_eq!(d.remove_layer("caption").unwrap().name(), "caption");
+        assert_eq!(d.layers().len(), 2);
+    }
+
+    #[test]
+    fn test_document_push_layer_resizes() {
+        let mut d = Document::new(3, 2);
+        d.push_layer(Layer::new("small", canvas("a"))).unwrap();
+
+        assert_eq!(d.layer("small").unwrap().canvas().width(), 3);
+        assert_eq!(d.layer("small").unwrap().canvas().height(), 2);
+
+        d.resize(4, 1);
+        assert_eq!(render(&d.flatten()), "a   ");
+    }
+
+    #[test]
+    fn test_document_padding_is_transparent() {
+        let mut d = Document::new(3, 2);
+        d.push_layer(Layer::new("background", canvas("...\n..."))).unwrap();
+        d.push_layer(Layer::new("small", canvas("a"))).unwrap();
+        d.layer_mut("small").unwrap().transparency = Transparency::Opaque;
+
+        assert_eq!(render(&d.flatten()), "a..\n...");
+
+        d.resize(4, 3);
+        d.layer_mut("background").unwrap().canvas_mut().unwrap().draw_character((3, 2), &Character::new('#', Style::default()), BlendMode::Overwrite);
+        assert_eq!(render(&d.flatten()), "a.. \n... \n   #");
+    }
+}
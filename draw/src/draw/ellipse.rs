@@ -0,0 +1,241 @@
+use super::{canvas::Canvas, character::{BlendMode, Character}};
+
+// Terminal cells are roughly twice as tall as they are wide.
+pub const CELL_ASPECT: usize = 2;
+
+fn signed(radius: usize) -> isize {
+    isize::try_from(radius).unwrap_or(isize::MAX)
+}
+
+fn quadrant_points(radii: (usize, usize)) -> Vec<(isize, isize)> {
+    let (rx, ry) = (signed(radii.0), signed(radii.1));
+    if ry == 0 {
+        return (0..=rx).map(|x| (x, 0)).collect();
+    }
+
+    let (rx2, ry2) = ((rx as f64).powi(2), (ry as f64).powi(2));
+    let mut points = Vec::new();
+    let (mut x, mut y) = (0, ry);
+    let (mut px, mut py) = (0.0, 2.0 * rx2 * y as f64);
+
+    let mut p = ry2 - rx2 * ry as f64 + rx2 / 4.0;
+    while px < py {
+        points.push((x, y));
+        x += 1;
+        px += 2.0 * ry2;
+        if p < 0.0 {
+            p += ry2 + px;
+        } else {
+            y -= 1;
+            py -= 2.0 * rx2;
+            p += ry2 + px - py;
+        }
+    }
+
+    p = ry2 * (x as f64 + 0.5).powi(2) + rx2 * ((y - 1) as f64).powi(2) - rx2 * ry2;
+    while y >= 0 {
+        points.push((x, y));
+        y -= 1;
+        py -= 2.0 * rx2;
+        if p > 0.0 {
+            p += rx2 - py;
+        } else {
+            x += 1;
+            px += 2.0 * ry2;
+            p += rx2 - py + px;
+        }
+    }
+
+    points
+}
+
+pub(crate) fn ellipse_points(radii: (usize, usize)) -> Vec<(isize, isize)> {
+    let mut points: Vec<_> = quadrant_points(radii).into_iter()
+        .flat_map(|(x, y)| [(x, y), (-x, y), (x, -y), (-x, -y)])
+        .collect();
+    points.sort_unstable();
+    points.dedup();
+    points
+}
+
+fn angle(point: (isize, isize), radii: (usize, usize)) -> f64 {
+    let x = point.0 as f64 / radii.0.max(1) as f64;
+    let y = -point.1 as f64 / radii.1.max(1) as f64;
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+impl Canvas {
+    fn draw_offsets(&mut self, center: (usize, usize), offsets: &[(isize, isize)], brush: &Character, mode: BlendMode) {
+        for (x, y) in offsets {
+            if let (Some(x), Some(y)) = (center.0.checked_add_signed(*x), center.1.checked_add_signed(*y)) {
+                self.draw_character((x, y), brush, mode);
+            }
+        }
+    }
+
+    pub fn draw_ellipse(&mut self, center: (usize, usize), radii: (usize, usize), brush: &Character, mode: BlendMode) {
+        self.draw_offsets(center, &ellipse_points(radii), brush, mode);
+    }
+
+    pub fn fill_ellipse(&mut self, center: (usize, usize), radii: (usize, usize), brush: &Character, mode: BlendMode) {
+        let outline = ellipse_points(radii);
+        let offsets: Vec<_> = (-signed(radii.1)..=signed(radii.1))
+            .flat_map(|y| {
+                let span = outline.iter()
+                    .filter(|p| p.1 == y)
+                    .map(|p| p.0)
+                    .max()
+                    .unwrap_or(0);
+                (-span..=span).map(move |x| (x, y))
+            })
+            .collect();
+
+        self.draw_offsets(center, &offsets, brush, mode);
+    }
+
+    pub fn draw_circle(&mut self, center: (usize, usize), radius: usize, brush: &Character, mode: BlendMode) {
+        self.draw_ellipse(center, (radius.saturating_mul(CELL_ASPECT), radius), brush, mode);
+    }
+
+    pub fn fill_circle(&mut self, center: (usize, usize), radius: usize, brush: &Character, mode: BlendMode) {
+        self.fill_ellipse(center, (radius.saturating_mul(CELL_ASPECT), radius), brush, mode);
+    }
+
+    pub fn draw_arc(
+        &mut self,
+        center: (usize, usize),
+        radii: (usize, usize),
+        angles: (f64, f64),
+        brush: &Character,
+        mode: BlendMode
+    ) {
+        let start = angles.0.rem_euclid(360.0);
+        let end = angles.1.rem_euclid(360.0);
+        let full = (angles.1 - angles.0).abs() >= 360.0;
+
+        let offsets: Vec<_> = ellipse_points(radii).into_iter()
+            .filter(|p| {
+                let a = angle(*p, radii);
+                full || if start <= end {
+                    start <= a && a <= end
+                } else {
+                    a >= start || a <= end
+                }
+            })
+            .collect();
+
+        self.draw_offsets(center, &offsets, brush, mode);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{style::{Style, Color, Modifiers}, test_helpers::render};
+
+    use super::*;
+
+    #[test]
+    fn test_ellipse_points_symmetric() {
+        let points = ellipse_points((5, 3));
+        for (x, y) in &points {
+            assert!(points.contains(&(-x, *y)));
+            assert!(points.contains(&(*x, -y)));
+        }
+        assert!(points.contains(&(5, 0)));
+        assert!(points.contains(&(0, 3)));
+        assert!(!points.contains(&(0, 0)));
+    }
+
+    #[test]
+    fn test_ellipse_points_degenerate() {
+        assert_eq!(
+            ellipse_points((2, 0)),
+            vec![(-2, 0), (-1, 0), (0, 0), (1, 0), (2, 0)]
+        );
+        assert_eq!(
+            ellipse_points((0, 1)),
+            vec![(0, -1), (0, 0), (0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_circle() {
+        let brush = Character::new('#', Style::new(Color::Red, Color::Black, Modifiers::new(true, false, false)));
+        let mut c = Canvas::new(9, 5);
+
+        c.draw_circle((4, 2), 2, &brush, BlendMode::Overwrite);
+
+        assert_eq!(c.row(0)[4], brush);
+        assert_eq!(
+            render(&c),
+            "  #####  \n #     # \n#       #\n #     # \n  #####  "
+        );
+    }
+
+    #[test]
+    fn test_canvas_fill_ellipse() {
+        let brush = Character::new('#', Style::default());
+        let mut c = Canvas::new(9, 5);
+
+        c.fill_ellipse((4, 2), (4, 2), &brush, BlendMode::Overwrite);
+
+        assert_eq!(
+            render(&c),
+            "  #####  \n ####### \n#########\n ####### \n  #####  "
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_ellipse_clips() {
+        let brush = Character::new('#', Style::default());
+        let mut c = Canvas::new(4, 3);
+
+        c.draw_ellipse((0, 0), (2, 1), &brush, BlendMode::Overwrite);
+
+        assert_eq!(
+            render(&c),
+            "  # \n##  \n    "
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_ellipse_far_away() {
+        let brush = Character::new('#', Style::default());
+        let mut c = Canvas::new(4, 3);
+
+        c.draw_ellipse((usize::MAX, 1), (2, 1), &brush, BlendMode::Overwrite);
+        c.fill_circle((1, usize::MAX - 1), 1, &brush, BlendMode::Overwrite);
+
+        assert_eq!(
+            render(&c),
+            "    \n    \n    "
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_arc() {
+        let brush = Character::new('#', Style::default());
+        let mut c = Canvas::new(9, 5);
+
+        c.draw_arc((4, 2), (4, 2), (0.0, 90.0), &brush, BlendMode::Overwrite);
+
+        assert_eq!(
+            render(&c),
+            "    ###  \n       # \n        #\n         \n         "
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_arc_wraps() {
+        let brush = Character::new('#', Style::default());
+        let mut c = Canvas::new(9, 5);
+
+        c.draw_arc((4, 2), (4, 2), (270.0, 90.0), &brush, BlendMode::Overwrite);
+
+        assert_eq!(
+            render(&c),
+            "    ###  \n       # \n        #\n       # \n    ###  "
+        );
+    }
+}
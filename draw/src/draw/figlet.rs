@@ -0,0 +1,528 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{canvas::Canvas, character::{BlendMode, Character}, style::Style};
+
+const SM_EQUAL: u32 = 1;
+const SM_LOWLINE: u32 = 2;
+const SM_HIERARCHY: u32 = 4;
+const SM_PAIR: u32 = 8;
+const SM_BIGX: u32 = 16;
+const SM_HARDBLANK: u32 = 32;
+const SM_KERN: u32 = 64;
+const SM_SMUSH: u32 = 128;
+const SM_RULES: u32 = 63;
+
+const DEUTSCH: [char; 7] = ['Ä', 'Ö', 'Ü', 'ä', 'ö', 'ü', 'ß'];
+
+pub const BUNDLED_FONTS: [&str; 3] = ["bubble", "digital", "standard"];
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum FigletLayout {
+    FullWidth,
+    Fitting,
+    Smushing
+}
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq
+)]
+pub enum FigletError {
+    InvalidHeader,
+    InvalidCodeTag(String),
+    UnexpectedEnd,
+    NoCharacters
+}
+
+impl Display for FigletError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FigletError::InvalidHeader => write!(f, "Invalid FIGlet font header"),
+            FigletError::InvalidCodeTag(t) => write!(f, "Invalid FIGlet code tag '{t}'"),
+            FigletError::UnexpectedEnd => write!(f, "FIGlet font ended in the middle of a character"),
+            FigletError::NoCharacters => write!(f, "FIGlet font doesn't define any characters")
+        }
+    }
+}
+
+impl std::error::Error for FigletError {}
+
+
+#[derive(Debug, Clone)]
+pub struct FigletFont {
+    hardblank: char,
+    height: usize,
+    baseline: usize,
+    layout: u32,
+    characters: HashMap<char, Vec<Vec<char>>>
+}
+
+fn parse_code(tag: &str) -> Option<i64> {
+    let (negative, tag) = match tag.strip_prefix('-') {
+        Some(t) => (true, t),
+        None => (false, tag)
+    };
+    let code = if let Some(hex) = tag.strip_prefix("0x").or_else(|| tag.strip_prefix("0X")) {
+        i64::from_str_radix(hex, 16).ok()?
+    } else if tag.len() > 1 && tag.starts_with('0') {
+        i64::from_str_radix(&tag[1..], 8).ok()?
+    } else {
+        tag.parse().ok()?
+    };
+    Some(if negative { -code } else { code })
+}
+
+fn parse_row(line: &str) -> Vec<char> {
+    let mut row: Vec<char> = line.trim_end().chars().collect();
+    if let Some(endmark) = row.last().copied() {
+        while row.last() == Some(&endmark) {
+            row.pop();
+        }
+    }
+    row
+}
+
+impl FigletFont {
+    pub fn parse(data: &str) -> Result<Self, FigletError> {
+        let mut lines = data.lines();
+        let header = lines.next().ok_or(FigletError::InvalidHeader)?;
+        let header = header.strip_prefix("flf2a").ok_or(FigletError::InvalidHeader)?;
+        let mut header_chars = header.chars();
+        let hardblank = header_chars.next().ok_or(FigletError::InvalidHeader)?;
+        let values = header_chars.as_str().split_whitespace()
+            .map(|v| v.parse::<i64>().map_err(|_| FigletError::InvalidHeader))
+            .collect::<Result<Vec<_>, _>>()?;
+        if values.len() < 5 || values[0] < 1 || values[4] < 0 {
+            return Err(FigletError::InvalidHeader);
+        }
+
+        let height = values[0] as usize;
+        let baseline = values[1].clamp(1, values[0]) as usize;
+        let layout = match (values.get(6), values[3]) {
+            (Some(full), _) => *full as u32 & (SM_RULES | SM_KERN | SM_SMUSH),
+            (None, -1) => 0,
+            (None, 0) => SM_KERN,
+            (None, old) => (old as u32 & SM_RULES) | SM_SMUSH
+        };
+
+        // The height comes from the header alone, so it's checked against the data before anything is sized by it.
+        let body: Vec<&str> = lines.skip(values[4] as usize).collect();
+        if body.is_empty() {
+            return Err(FigletError::NoCharacters);
+        }
+        if body.len() < height {
+            return Err(FigletError::UnexpectedEnd);
+        }
+
+        let mut lines = body.into_iter().peekable();
+        let read_character = |lines: &mut std::iter::Peekable<_>| -> Result<Vec<Vec<char>>, FigletError> {
+            let mut rows = (0..height).map(|_| {
+                lines.next().map(parse_row).ok_or(FigletError::UnexpectedEnd)
+            }).collect::<Result<Vec<_>, _>>()?;
+
+            // Rendering assumes every row of a glyph is equally wide, which fonts don't always keep to.
+            let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+            for r in rows.iter_mut() {
+                r.resize(width, ' ');
+            }
+            Ok(rows)
+        };
+
+        let mut characters = HashMap::new();
+        for c in (' '..='~').chain(DEUTSCH) {
+            if lines.peek().is_none() {
+                break;
+            }
+            characters.insert(c, read_character(&mut lines)?);
+        }
+
+        while let Some(tag) = lines.next() {
+            let tag = tag.split_whitespace().next().unwrap_or_default();
+            if tag.is_empty() {
+                continue;
+            }
+
+            let code = parse_code(tag).ok_or_else(|| FigletError::InvalidCodeTag(tag.to_string()))?;
+            let character = read_character(&mut lines)?;
+            if let Some(c) = u32::try_from(code).ok().and_then(char::from_u32) {
+                characters.insert(c, character);
+            }
+        }
+
+        Ok(Self {
+            hardblank,
+            height,
+            baseline,
+            layout,
+            characters
+        })
+    }
+
+    pub fn bundled(name: &str) -> Option<Self> {
+        let data = match name {
+            "bubble" => include_str!("figlet/bubble.flf"),
+            "digital" => include_str!("figlet/digital.flf"),
+            "standard" => include_str!("figlet/standard.flf"),
+            _ => return None
+        };
+        Some(Self::parse(data).expect("Bundled FIGlet fonts should be valid"))
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn baseline(&self) -> usize {
+        self.baseline
+    }
+
+    pub fn default_layout(&self) -> FigletLayout {
+        if self.layout & SM_SMUSH != 0 {
+            FigletLayout::Smushing
+        } else if self.layout & SM_KERN != 0 {
+            FigletLayout::Fitting
+        } else {
+            FigletLayout::FullWidth
+        }
+    }
+
+    fn mode(&self, layout: FigletLayout) -> u32 {
+        match layout {
+            FigletLayout::FullWidth => 0,
+            FigletLayout::Fitting => SM_KERN,
+            FigletLayout::Smushing => SM_SMUSH | (self.layout & SM_RULES)
+        }
+    }
+
+    fn smush(&self, mode: u32, widths: (usize, usize), l: char, r: char) -> Option<char> {
+        if l == ' ' {
+            return Some(r);
+        }
+        if r == ' ' {
+            return Some(l);
+        }
+        if widths.0 < 2 || widths.1 < 2 || mode & SM_SMUSH == 0 {
+            return None;
+        }
+
+        let hardblank = self.hardblank;
+        if mode & SM_RULES == 0 {
+            return Some(if l == hardblank { r } else if r == hardblank { l } else { r });
+        }
+
+        if mode & SM_HARDBLANK != 0 && l == hardblank && r == hardblank {
+            return Some(l);
+        }
+        if l == hardblank || r == hardblank {
+            return None;
+        }
+        if mode & SM_EQUAL != 0 && l == r {
+            return Some(l);
+        }
+        if mode & SM_LOWLINE != 0 {
+            if l == '_' && "|/\\[]{}()<>".contains(r) {
+                return Some(r);
+            }
+            if r == '_' && "|/\\[]{}()<>".contains(l) {
+                return Some(l);
+            }
+        }
+        if mode & SM_HIERARCHY != 0 {
+            let classes = ["|", "/\\", "[]", "{}", "()", "<>"];
+            let class = |c: char| classes.iter().position(|k| k.contains(c));
+            if let (Some(lc), Some(rc)) = (class(l), class(r)) {
+                if lc < rc {
+                    return Some(r);
+                }
+                if rc < lc {
+                    return Some(l);
+                }
+            }
+        }
+        if mode & SM_PAIR != 0 && ["[]", "][", "{}", "}{", "()", ")("].contains(&[l, r].iter().collect::<String>().as_str()) {
+            return Some('|');
+        }
+        if mode & SM_BIGX != 0 {
+            match (l, r) {
+                ('/', '\\') => return Some('|'),
+                ('\\', '/') => return Some('Y'),
+                ('>', '<') => return Some('X'),
+                _ => ()
+            }
+        }
+        None
+    }
+
+    fn smush_amount(&self, mode: u32, widths: (usize, usize), output: &[Vec<char>], glyph: &[Vec<char>]) -> usize {
+        if mode & (SM_SMUSH | SM_KERN) == 0 {
+            return 0;
+        }
+
+        output.iter().zip(glyph).map(|(line, row)| {
+            let trailing = line.iter().rev().take_while(|c| **c == ' ').count();
+            let leading = row.iter().take_while(|c| **c == ' ').count();
+            let l = line.len().checked_sub(trailing + 1).map(|i| line[i]);
+            let r = row.get(leading).copied();
+
+            match (l, r) {
+                (Some(l), Some(r)) if self.smush(mode, widths, l, r).is_some() => leading + trailing + 1,
+                _ => leading + trailing
+            }
+        }).min().unwrap_or(0).min(glyph.first().map_or(0, |r| r.len()))
+    }
+
+    fn render_line(&self, text: &str, mode: u32) -> Vec<Vec<char>> {
+        let mut output = vec![Vec::new(); self.height];
+        let mut previous_width = 0;
+        for c in text.chars() {
+            let c = if c == '\t' { ' ' } else { c };
+            let glyph = match self.characters.get(&c) {
+                Some(g) => g,
+                None => continue
+            };
+
+            let width = glyph.first().map_or(0, |r| r.len());
+            let widths = (previous_width, width);
+            let amount = self.smush_amount(mode, widths, &output, glyph).min(output[0].len());
+            for (line, row) in output.iter_mut().zip(glyph) {
+                let amount = amount.min(line.len());
+                let start = line.len() - amount;
+                for (k, r) in row.iter().take(amount).enumerate() {
+                    line[start + k] = self.smush(mode, widths, line[start + k], *r).unwrap_or(*r);
+                }
+                line.extend_from_slice(&row[amount.min(row.len())..]);
+            }
+            previous_width = width;
+        }
+
+        for line in output.iter_mut() {
+            for c in line.iter_mut().filter(|c| **c == self.hardblank) {
+                *c = ' ';
+            }
+        }
+        output
+    }
+
+    pub fn render_lines(&self, text: &str, layout: FigletLayout) -> Vec<Vec<char>> {
+        let mode = self.mode(layout);
+        let mut lines: Vec<Vec<char>> = text.lines()
+            .flat_map(|l| self.render_line(l, mode))
+            .collect();
+
+        let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+        for l in lines.iter_mut() {
+            l.resize(width, ' ');
+        }
+        lines
+    }
+
+    pub fn render(&self, text: &str, style: Style, layout: FigletLayout) -> Canvas {
+        let mut lines = self.render_lines(text, layout);
+        if lines.is_empty() {
+            lines = vec![Vec::new(); self.height];
+        }
+
+        Canvas::new_with_buffer(
+            lines.iter().map(|l| l.iter().map(|c| Character::new(*c, style)).collect()).collect()
+        )
+    }
+}
+
+
+impl Canvas {
+    pub fn draw_figlet(&mut self, point: (usize, usize), font: &FigletFont, text: &str, style: Style, layout: FigletLayout) {
+        for (y, l) in font.render_lines(text, layout).iter().enumerate() {
+            let Some(y) = point.1.checked_add(y) else {
+                break;
+            };
+            for (x, c) in l.iter().enumerate().filter(|(_, c)| **c != ' ') {
+                let Some(x) = point.0.checked_add(x) else {
+                    break;
+                };
+                self.draw_character((x, y), &Character::new(*c, style), BlendMode::Overwrite);
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{style::{Color, Modifiers}, test_helpers::render as render_canvas};
+
+    use super::*;
+
+    fn font_source() -> String {
+        let mut s = String::from("flf2a$ 3 2 6 15 2 0 143 1\nTest font\nwith two comment lines\n");
+        s += "$$@\n$$@\n$$@@\n";
+        s += "/\\ @\n\\/ @\n   @@\n";
+        s += "|  @\n|_ @\n   @@\n";
+        s += " _ @\n[  @\n ] @@\n";
+        for _ in 4..102 {
+            s += "@\n@\n@@\n";
+        }
+        s += "0x100  LATIN CAPITAL LETTER A WITH MACRON\n-|-#\n | #\n | ##\n";
+        s
+    }
+
+    fn render(lines: &[Vec<char>]) -> String {
+        lines.iter().map(|l| l.iter().collect::<String>()).collect::<Vec<_>>().join("\n")
+    }
+
+    fn font() -> FigletFont {
+        FigletFont::parse(&font_source()).unwrap()
+    }
+
+    #[test]
+    fn test_figlet_parse() {
+        let f = font();
+
+        assert_eq!(f.height(), 3);
+        assert_eq!(f.baseline(), 2);
+        assert_eq!(f.hardblank, '$');
+        assert_eq!(f.default_layout(), FigletLayout::Smushing);
+        assert_eq!(f.characters[&'!'], vec![vec!['/', '\\', ' '], vec!['\\', '/', ' '], vec![' ', ' ', ' ']]);
+        assert_eq!(f.characters[&'Ā'], vec![vec!['-', '|', '-'], vec![' ', '|', ' '], vec![' ', '|', ' ']]);
+        assert!(!f.characters.contains_key(&'€'));
+    }
+
+    #[test]
+    fn test_figlet_parse_errors() {
+        assert_eq!(FigletFont::parse("flf2 3 2").unwrap_err(), FigletError::InvalidHeader);
+        assert_eq!(FigletFont::parse("flf2a$ 3").unwrap_err(), FigletError::InvalidHeader);
+        assert_eq!(FigletFont::parse("flf2a$ 2 2 2 0 0\na@\n").unwrap_err(), FigletError::UnexpectedEnd);
+        assert_eq!(
+            FigletFont::parse(&(font_source() + "zz\n")).unwrap_err(),
+            FigletError::InvalidCodeTag("zz".to_string())
+        );
+        assert_eq!(
+            FigletFont::parse(&(font_source() + "0x41\na@\n")).unwrap_err(),
+            FigletError::UnexpectedEnd
+        );
+        assert_eq!(FigletFont::parse("flf2a$ 100000000000 1 2 0 0\n").unwrap_err(), FigletError::NoCharacters);
+        assert_eq!(FigletFont::parse("flf2a$ 100000000000 1 2 0 0\na@\nb@@\n").unwrap_err(), FigletError::UnexpectedEnd);
+        assert_eq!(FigletFont::parse("flf2a$ 1 1 2 0 1\ncomment\n").unwrap_err(), FigletError::NoCharacters);
+    }
+
+    #[test]
+    fn test_figlet_parse_uneven_rows() {
+        let f = FigletFont::parse("flf2a$ 2 2 4 0 0\n@\n@@\nab@\n@@\n c@\n d@@\n").unwrap();
+
+        assert_eq!(f.characters[&'!'], vec![vec!['a', 'b'], vec![' ', ' ']]);
+        assert_eq!(render(&f.render_lines("!\"", FigletLayout::Fitting)), "abc\n  d");
+    }
+
+    #[test]
+    fn test_figlet_parse_old_layout() {
+        assert_eq!(FigletFont::parse("flf2a$ 1 1 2 -1 0\n @@\n").unwrap().default_layout(), FigletLayout::FullWidth);
+        assert_eq!(FigletFont::parse("flf2a$ 1 1 2 0 0\n @@\n").unwrap().default_layout(), FigletLayout::Fitting);
+        assert_eq!(FigletFont::parse("flf2a$ 1 1 2 1 0\n @@\n").unwrap().default_layout(), FigletLayout::Smushing);
+    }
+
+    #[test]
+    fn test_figlet_full_width() {
+        assert_eq!(
+            render(&font().render_lines("!\"", FigletLayout::FullWidth)),
+            "/\\ |  \n\\/ |_ \n      "
+        );
+    }
+
+    #[test]
+    fn test_figlet_fitting() {
+        assert_eq!(
+            render(&font().render_lines("!\"", FigletLayout::Fitting)),
+            "/\\|  \n\\/|_ \n     "
+        );
+    }
+
+    #[test]
+    fn test_figlet_smushing() {
+        assert_eq!(
+            render(&font().render_lines("!\"#", FigletLayout::Smushing)),
+            "/\\ _ \n\\/[  \n   ] "
+        );
+    }
+
+    #[test]
+    fn test_figlet_hardblank() {
+        assert_eq!(
+            render(&font().render_lines("! !", FigletLayout::Fitting)),
+            "/\\  /\\ \n\\/  \\/ \n       "
+        );
+    }
+
+    #[test]
+    fn test_figlet_multiple_lines() {
+        let lines = font().render_lines("\"\n!!", FigletLayout::FullWidth);
+
+        assert_eq!(lines.len(), 6);
+        assert!(lines.iter().all(|l| l.len() == 6));
+    }
+
+    #[test]
+    fn test_figlet_bundled() {
+        for name in BUNDLED_FONTS {
+            let f = FigletFont::bundled(name).unwrap();
+            for c in ' '..='~' {
+                assert!(f.characters.contains_key(&c), "{name} is missing {c}");
+            }
+        }
+        assert!(FigletFont::bundled("missing").is_none());
+
+        let standard = FigletFont::bundled("standard").unwrap();
+        assert_eq!(standard.default_layout(), FigletLayout::Smushing);
+        assert_eq!(
+            render(&standard.render_lines("Hi", FigletLayout::Smushing)),
+            "  _   _ _ \n | | | (_)\n | |_| | |\n |  _  | |\n |_| |_|_|\n          "
+        );
+
+        let digital = FigletFont::bundled("digital").unwrap();
+        assert_eq!(
+            render(&digital.render_lines("Hi $", FigletLayout::Smushing)),
+            "+-+-+ +-+\n|H|i| |$|\n+-+-+ +-+"
+        );
+
+        let bubble = FigletFont::bundled("bubble").unwrap();
+        assert_eq!(
+            render(&bubble.render_lines("Hi", FigletLayout::Smushing)),
+            "  _   _  \n / \\ / \\ \n( H | i )\n \\_/ \\_/ "
+        );
+    }
+
+    #[test]
+    fn test_figlet_render_canvas() {
+        let style = Style::new(Color::Red, Color::Black, Modifiers::new(true, false, false));
+        let c = font().render("!", style, FigletLayout::FullWidth);
+
+        assert_eq!(c.width(), 3);
+        assert_eq!(c.height(), 3);
+        assert_eq!(c.row(0)[0], Character::new('/', style));
+    }
+
+    #[test]
+    fn test_canvas_draw_figlet() {
+        let mut c = Canvas::new_filled(4, 3, Character::new('.', Style::default()));
+
+        c.draw_figlet((1, 0), &font(), "!", Style::default(), FigletLayout::FullWidth);
+
+        assert_eq!(
+            render_canvas(&c),
+            "./\\.\n.\\/.\n...."
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_figlet_far_away() {
+        let mut c = Canvas::new_filled(2, 2, Character::new('.', Style::default()));
+
+        c.draw_figlet((usize::MAX - 1, 0), &font(), "!!", Style::default(), FigletLayout::FullWidth);
+        c.draw_figlet((0, usize::MAX), &font(), "!", Style::default(), FigletLayout::FullWidth);
+
+        assert_eq!(render_canvas(&c), "..\n..");
+    }
+}
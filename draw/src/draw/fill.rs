@@ -0,0 +1,212 @@
+use super::{canvas::Canvas, character::{BlendMode, Character}};
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum FillMatch {
+    Character,
+    Style,
+    Foreground,
+    Background,
+    Exact
+}
+
+impl FillMatch {
+    fn matches(&self, target: &Character, other: &Character) -> bool {
+        match self {
+            FillMatch::Character => target.character() == other.character(),
+            FillMatch::Style => target.style() == other.style(),
+            FillMatch::Foreground => target.style().fg == other.style().fg,
+            FillMatch::Background => target.style().bg == other.style().bg,
+            FillMatch::Exact => target == other
+        }
+    }
+}
+
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum Connectivity {
+    Four,
+    Eight
+}
+
+impl Connectivity {
+    fn neighbours(&self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &[(1, 0), (-1, 0), (0, 1), (0, -1)],
+            Connectivity::Eight => &[(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)]
+        }
+    }
+}
+
+
+impl Canvas {
+    pub fn flood_fill(
+        &mut self,
+        point: (usize, usize),
+        brush: &Character,
+        mode: BlendMode,
+        criterion: FillMatch,
+        connectivity: Connectivity
+    ) {
+        let target = match self.get(point.0, point.1) {
+            Some(c) => c.clone(),
+            None => return
+        };
+        let (width, height) = (self.width() as isize, self.height() as isize);
+
+        let mut visited = vec![false; self.width() * self.height()];
+        let mut stack = vec![point];
+        visited[point.1 * self.width() + point.0] = true;
+
+        // The region is found before anything is drawn, wide brushes change the cells next to the ones they're drawn on.
+        let mut region = vec![false; self.width() * self.height()];
+        while let Some((x, y)) = stack.pop() {
+            if !criterion.matches(&target, &self.row(y)[x]) {
+                continue;
+            }
+            region[y * self.width() + x] = true;
+
+            for (dx, dy) in connectivity.neighbours() {
+                let (nx, ny) = (x as isize + dx, y as isize + dy);
+                if nx < 0 || ny < 0 || nx >= width || ny >= height {
+                    continue;
+                }
+
+                let (nx, ny) = (nx as usize, ny as usize);
+                let i = ny * self.width() + nx;
+                if !visited[i] {
+                    visited[i] = true;
+                    stack.push((nx, ny));
+                }
+            }
+        }
+
+        for (i, _) in region.iter().enumerate().filter(|(_, r)| **r) {
+            self.draw_character((i % self.width(), i / self.width()), brush, mode);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{style::{Style, Color, Modifiers}, test_helpers::{render, canvas}};
+
+    use super::*;
+
+    #[test]
+    fn test_canvas_flood_fill_four() {
+        let mut c = canvas("..#..\n..#..\n##...\n.....");
+
+        c.flood_fill((0, 0), &Character::new('o', Style::default()), BlendMode::Overwrite, FillMatch::Character, Connectivity::Four);
+
+        assert_eq!(
+            render(&c),
+            "oo#..\noo#..\n##...\n....."
+        );
+    }
+
+    #[test]
+    fn test_canvas_flood_fill_eight() {
+        let mut c = canvas("..#..\n..#..\n##...\n.....");
+
+        c.flood_fill((0, 0), &Character::new('o', Style::default()), BlendMode::Overwrite, FillMatch::Character, Connectivity::Eight);
+
+        assert_eq!(
+            render(&c),
+            "oo#oo\noo#oo\n##ooo\nooooo"
+        );
+    }
+
+    #[test]
+    fn test_canvas_flood_fill_wide() {
+        let mut c = canvas("....#\n.....");
+
+        c.flood_fill((0, 0), &Character::new('漢', Style::default()), BlendMode::Overwrite, FillMatch::Character, Connectivity::Four);
+
+        assert_eq!(
+            render(&c),
+            "漢漢#\n漢漢 "
+        );
+    }
+
+    #[test]
+    fn test_canvas_flood_fill_out_of_bounds() {
+        let mut c = canvas("..\n..");
+
+        c.flood_fill((5, 0), &Character::new('o', Style::default()), BlendMode::Overwrite, FillMatch::Exact, Connectivity::Four);
+
+        assert_eq!(
+            render(&c),
+            "..\n.."
+        );
+    }
+
+    #[test]
+    fn test_canvas_flood_fill_matching_brush_terminates() {
+        let mut c = canvas("...\n...");
+
+        c.flood_fill((1, 1), &Character::new('.', Style::default()), BlendMode::Overwrite, FillMatch::Character, Connectivity::Eight);
+
+        assert_eq!(
+            render(&c),
+            "...\n..."
+        );
+    }
+
+    #[test]
+    fn test_canvas_flood_fill_by_background() {
+        let red = Character::new('a', Style::new(Color::None, Color::Red, Modifiers::default()));
+        let other = Character::new('a', Style::new(Color::None, Color::Red, Modifiers::new(true, false, false)));
+        let blue = Character::new('a', Style::new(Color::None, Color::Blue, Modifiers::default()));
+        let brush = Character::new('#', Style::new(Color::Green, Color::Yellow, Modifiers::default()));
+        let mut c = Canvas::new_with_buffer(vec![
+            vec![red, other, blue.clone()]
+        ]);
+
+        c.flood_fill((0, 0), &brush, BlendMode::OnlyStyle, FillMatch::Background, Connectivity::Four);
+
+        assert_eq!(
+            c.to_buffer(),
+            vec![vec![
+                Character::new('a', *brush.style()),
+                Character::new('a', *brush.style()),
+                blue
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_canvas_flood_fill_by_style() {
+        let red = Character::new('a', Style::new(Color::None, Color::Red, Modifiers::default()));
+        let other = Character::new('b', Style::new(Color::None, Color::Red, Modifiers::default()));
+        let bold = Character::new('a', Style::new(Color::None, Color::Red, Modifiers::new(true, false, false)));
+        let brush = Character::new('#', Style::default());
+        let mut c = Canvas::new_with_buffer(vec![
+            vec![red, other, bold.clone()]
+        ]);
+
+        c.flood_fill((0, 0), &brush, BlendMode::Overwrite, FillMatch::Style, Connectivity::Four);
+
+        assert_eq!(
+            c.to_buffer(),
+            vec![vec![brush.clone(), brush, bold]]
+        );
+    }
+
+    #[test]
+    fn test_canvas_flood_fill_large() {
+        let mut c = Canvas::new(500, 500);
+
+        c.flood_fill((250, 250), &Character::new('#', Style::default()), BlendMode::Overwrite, FillMatch::Exact, Connectivity::Four);
+
+        assert!(c.cells().iter().all(|c| c.character() == '#'));
+    }
+}
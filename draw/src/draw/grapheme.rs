@@ -0,0 +1,115 @@
+use std::fmt::Display;
+
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use smallvec::SmallVec;
+use unicode_width::UnicodeWidthChar;
+
+const VARIATION_SELECTOR_EMOJI: char = '\u{FE0F}';
+
+// UTF-8 bytes of a single grapheme cluster, kept inline unless the cluster is unusually long.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq,
+    Hash
+)]
+pub struct Grapheme(SmallVec<[u8; 16]>);
+
+impl Grapheme {
+    pub fn new(grapheme: &str) -> Self {
+        if grapheme.is_empty() {
+            Self::from(' ')
+        } else {
+            Self(SmallVec::from_slice(grapheme.as_bytes()))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("Grapheme should hold valid UTF-8")
+    }
+
+    pub fn base(&self) -> char {
+        self.as_str().chars().next().unwrap_or(' ')
+    }
+
+    pub fn width(&self) -> usize {
+        if self.as_str().contains(VARIATION_SELECTOR_EMOJI) {
+            2
+        } else {
+            self.base().width().unwrap_or(1).clamp(1, 2)
+        }
+    }
+}
+
+impl From<char> for Grapheme {
+    fn from(character: char) -> Self {
+        let mut buffer = [0; 4];
+        Self(SmallVec::from_slice(character.encode_utf8(&mut buffer).as_bytes()))
+    }
+}
+
+impl From<&str> for Grapheme {
+    fn from(grapheme: &str) -> Self {
+        Self::new(grapheme)
+    }
+}
+
+impl Display for Grapheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Grapheme {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Grapheme {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let grapheme = String::deserialize(deserializer)?;
+        Ok(Self::new(&grapheme))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grapheme_from_char() {
+        assert_eq!(Grapheme::from('a').as_str(), "a");
+        assert_eq!(Grapheme::from('漢').as_str(), "漢");
+        assert_eq!(Grapheme::from('a'), Grapheme::new("a"));
+    }
+
+    #[test]
+    fn test_grapheme_empty() {
+        assert_eq!(Grapheme::new(""), Grapheme::from(' '));
+    }
+
+    #[test]
+    fn test_grapheme_base() {
+        assert_eq!(Grapheme::new("e\u{301}").base(), 'e');
+        assert_eq!(Grapheme::new("👩\u{200D}💻").base(), '👩');
+    }
+
+    #[test]
+    fn test_grapheme_width() {
+        assert_eq!(Grapheme::new("a").width(), 1);
+        assert_eq!(Grapheme::new("e\u{301}").width(), 1);
+        assert_eq!(Grapheme::new("漢").width(), 2);
+        assert_eq!(Grapheme::new("👩\u{200D}👩\u{200D}👧\u{200D}👦").width(), 2);
+        assert_eq!(Grapheme::new("❤\u{FE0F}").width(), 2);
+        assert_eq!(Grapheme::new("\u{301}").width(), 1);
+    }
+
+    #[test]
+    fn test_grapheme_long_cluster() {
+        let family = "👩\u{200D}👩\u{200D}👧\u{200D}👦";
+        assert_eq!(Grapheme::new(family).as_str(), family);
+        assert_eq!(Grapheme::new(family).to_string(), family);
+    }
+}
@@ -0,0 +1,393 @@
+use std::collections::VecDeque;
+
+use super::{canvas::Canvas, character::{BlendMode, Character}, diff::{self, CanvasDiff}, rect::Rect};
+
+pub const DEFAULT_MEMORY_LIMIT: usize = 16 * 1024 * 1024;
+
+// Every command is stored as the diff it made, one undo step may hold several of them.
+type Step = Vec<CanvasDiff>;
+
+fn step_memory(step: &Step) -> usize {
+    step.iter().map(|d| d.len() * 2 * std::mem::size_of::<Character>()).sum()
+}
+
+#[derive(Debug, Clone)]
+pub struct History {
+    undo: VecDeque<Step>,
+    redo: Vec<Step>,
+    transaction: Option<Step>,
+    depth: usize,
+    memory: usize,
+    memory_limit: usize
+}
+
+impl History {
+    pub fn new(memory_limit: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            transaction: None,
+            depth: 0,
+            memory: 0,
+            memory_limit
+        }
+    }
+
+    pub fn memory(&self) -> usize {
+        self.memory
+    }
+
+    pub fn memory_limit(&self) -> usize {
+        self.memory_limit
+    }
+
+    pub fn set_memory_limit(&mut self, memory_limit: usize) {
+        self.memory_limit = memory_limit;
+        self.trim();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.transaction = None;
+        self.depth = 0;
+        self.memory = 0;
+    }
+
+    pub fn begin_transaction(&mut self) {
+        self.depth += 1;
+        self.transaction.get_or_insert_with(Vec::new);
+    }
+
+    pub fn end_transaction(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+        if self.depth == 0 {
+            if let Some(step) = self.transaction.take().filter(|s| !s.is_empty()) {
+                self.push(step);
+            }
+        }
+    }
+
+    pub fn record(&mut self, diff: CanvasDiff) {
+        if diff.is_empty() {
+            return;
+        }
+
+        match self.transaction.as_mut() {
+            Some(step) => step.push(diff),
+            None => self.push(vec![diff])
+        }
+    }
+
+    pub fn perform(&mut self, canvas: &mut Canvas, operation: impl FnOnce(&mut Canvas)) {
+        let before = canvas.crop_cells(canvas.bounds());
+        operation(canvas);
+        self.record(diff::diff(&before, canvas));
+    }
+
+    // Like `perform`, for operations that only touch cells inside of `region`.
+    pub fn perform_in_region(&mut self, canvas: &mut Canvas, region: Rect, operation: impl FnOnce(&mut Canvas)) {
+        let region = region.intersection(&canvas.bounds());
+        let before = canvas.crop_cells(region);
+        operation(canvas);
+        let after = canvas.crop_cells(region);
+        self.record(diff::diff(&before, &after).offset((region.x, region.y), (canvas.width(), canvas.height())));
+    }
+
+    pub fn draw_character(&mut self, canvas: &mut Canvas, point: (usize, usize), character: &Character, mode: BlendMode) {
+        // Wide glyphs can change the cells on both sides of the point.
+        let region = Rect::new(point.0.saturating_sub(1), point.1, 4, 1);
+        self.perform_in_region(canvas, region, |c| c.draw_character(point, character, mode));
+    }
+
+    pub fn resize(&mut self, canvas: &mut Canvas, width: usize, height: usize) {
+        self.perform(canvas, |c| c.resize(width, height));
+    }
+
+    pub fn undo(&mut self, canvas: &mut Canvas) -> bool {
+        // Stepping back in the middle of a transaction would leave its diffs applied to the wrong state.
+        if self.depth > 0 {
+            return false;
+        }
+
+        match self.undo.pop_back() {
+            Some(step) => {
+                for d in step.iter().rev() {
+                    d.revert(canvas);
+                }
+                self.redo.push(step);
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn redo(&mut self, canvas: &mut Canvas) -> bool {
+        if self.depth > 0 {
+            return false;
+        }
+
+        match self.redo.pop() {
+            Some(step) => {
+                for d in step.iter() {
+                    d.apply(canvas);
+                }
+                self.undo.push_back(step);
+                true
+            },
+            None => false
+        }
+    }
+
+    fn push(&mut self, step: Step) {
+        for s in self.redo.drain(..) {
+            self.memory -= step_memory(&s);
+        }
+        self.memory += step_memory(&step);
+        self.undo.push_back(step);
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.memory > self.memory_limit {
+            // The newest step stays even when it alone is over the limit.
+            let step = if self.undo.len() > 1 {
+                self.undo.pop_front().unwrap()
+            } else if !self.redo.is_empty() {
+                self.redo.remove(0)
+            } else {
+                break;
+            };
+            self.memory -= step_memory(&step);
+        }
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_MEMORY_LIMIT)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{style::{Color, Modifiers, Style}, test_helpers::{render, canvas}};
+
+    use super::*;
+
+    fn brush(c: char) -> Character {
+        Character::new(c, Style::default())
+    }
+
+    #[test]
+    fn test_history_undo_redo() {
+        let mut c = canvas("...\n...");
+        let mut h = History::default();
+
+        h.draw_character(&mut c, (0, 0), &brush('a'), BlendMode::Overwrite);
+        h.draw_character(&mut c, (2, 1), &brush('b'), BlendMode::Overwrite);
+        assert_eq!(render(&c), "a..\n..b");
+
+        assert!(h.undo(&mut c));
+        assert_eq!(render(&c), "a..\n...");
+        assert!(h.undo(&mut c));
+        assert_eq!(render(&c), "...\n...");
+        assert!(!h.undo(&mut c));
+
+        assert!(h.redo(&mut c));
+        assert_eq!(render(&c), "a..\n...");
+        assert!(h.redo(&mut c));
+        assert!(!h.redo(&mut c));
+        assert_eq!(render(&c), "a..\n..b");
+    }
+
+    #[test]
+    fn test_history_new_step_clears_redo() {
+        let mut c = canvas("...");
+        let mut h = History::default();
+
+        h.draw_character(&mut c, (0, 0), &brush('a'), BlendMode::Overwrite);
+        h.undo(&mut c);
+        assert!(h.can_redo());
+
+        h.draw_character(&mut c, (1, 0), &brush('b'), BlendMode::Overwrite);
+        assert!(!h.can_redo());
+        assert_eq!(render(&c), ".b.");
+    }
+
+    #[test]
+    fn test_history_ignores_no_op() {
+        let mut c = canvas("a");
+        let mut h = History::default();
+
+        h.draw_character(&mut c, (0, 0), &brush('a'), BlendMode::Overwrite);
+        h.draw_character(&mut c, (5, 5), &brush('b'), BlendMode::Overwrite);
+
+        assert!(!h.can_undo());
+        assert_eq!(h.memory(), 0);
+    }
+
+    #[test]
+    fn test_history_transaction() {
+        let mut c = canvas("....");
+        let mut h = History::default();
+
+        h.begin_transaction();
+        for x in 0..3 {
+            h.draw_character(&mut c, (x, 0), &brush('-'), BlendMode::Overwrite);
+        }
+        h.begin_transaction();
+        h.draw_character(&mut c, (3, 0), &brush('>'), BlendMode::Overwrite);
+        h.end_transaction();
+        assert!(!h.can_undo());
+        h.end_transaction();
+        assert_eq!(render(&c), "--->");
+
+        h.undo(&mut c);
+        assert_eq!(render(&c), "....");
+        assert!(!h.can_undo());
+
+        h.redo(&mut c);
+        assert_eq!(render(&c), "--->");
+    }
+
+    #[test]
+    fn test_history_undo_in_transaction() {
+        let mut c = canvas("...");
+        let mut h = History::default();
+
+        h.draw_character(&mut c, (0, 0), &brush('a'), BlendMode::Overwrite);
+        h.begin_transaction();
+        h.draw_character(&mut c, (1, 0), &brush('b'), BlendMode::Overwrite);
+        assert!(!h.undo(&mut c));
+        assert!(!h.redo(&mut c));
+        assert_eq!(render(&c), "ab.");
+
+        h.end_transaction();
+        assert!(h.undo(&mut c));
+        assert_eq!(render(&c), "a..");
+    }
+
+    #[test]
+    fn test_history_clear() {
+        let mut c = canvas("...");
+        let mut h = History::default();
+
+        h.draw_character(&mut c, (0, 0), &brush('a'), BlendMode::Overwrite);
+        h.begin_transaction();
+        h.draw_character(&mut c, (1, 0), &brush('b'), BlendMode::Overwrite);
+        h.clear();
+        assert!(!h.can_undo());
+        assert_eq!(h.memory(), 0);
+
+        h.draw_character(&mut c, (2, 0), &brush('c'), BlendMode::Overwrite);
+        assert!(h.undo(&mut c));
+        assert_eq!(render(&c), "ab.");
+        assert!(!h.undo(&mut c));
+    }
+
+    #[test]
+    fn test_history_resize() {
+        let mut c = canvas("ab\ncd");
+        let mut h = History::default();
+
+        h.resize(&mut c, 1, 1);
+        h.draw_character(&mut c, (0, 0), &brush('x'), BlendMode::Overwrite);
+        h.resize(&mut c, 3, 1);
+        assert_eq!(render(&c), "x  ");
+
+        h.undo(&mut c);
+        h.undo(&mut c);
+        h.undo(&mut c);
+        assert_eq!(render(&c), "ab\ncd");
+
+        h.redo(&mut c);
+        assert_eq!(render(&c), "a");
+    }
+
+    #[test]
+    fn test_history_perform() {
+        let mut c = canvas("ab\ncd");
+        let mut h = History::default();
+
+        h.perform(&mut c, |c| c.for_each_cell(|_, c| *c = brush('#')));
+        assert_eq!(render(&c), "##\n##");
+
+        h.undo(&mut c);
+        assert_eq!(render(&c), "ab\ncd");
+    }
+
+    #[test]
+    fn test_history_wide() {
+        let mut c = canvas("abcd");
+        let mut h = History::default();
+
+        h.draw_character(&mut c, (1, 0), &brush('漢'), BlendMode::Overwrite);
+        h.draw_character(&mut c, (2, 0), &brush('x'), BlendMode::Overwrite);
+        assert_eq!(render(&c), "a xd");
+
+        h.undo(&mut c);
+        assert_eq!(render(&c), "a漢d");
+        h.undo(&mut c);
+        assert_eq!(render(&c), "abcd");
+    }
+
+    #[test]
+    fn test_history_perform_in_region_wide() {
+        let mut c = canvas(" 漢~ ");
+        let mut h = History::default();
+        let style = Character::new_transparent(Style::new(Color::Red, Color::None, Modifiers::default()));
+
+        h.perform_in_region(&mut c, Rect::new(1, 0, 1, 1), |c| c.draw_character((1, 0), &style, BlendMode::OnlyStyle));
+        h.undo(&mut c);
+
+        assert_eq!(render(&c), " 漢~ ");
+        assert!(c.row(0)[2].is_continuation());
+    }
+
+    #[test]
+    fn test_history_keeps_newest_step() {
+        let mut c = Canvas::new(100, 100);
+        let mut h = History::new(1000);
+
+        h.perform(&mut c, |c| c.for_each_cell(|_, c| *c = brush('#')));
+        assert!(h.can_undo());
+        assert!(h.memory() > h.memory_limit());
+
+        h.draw_character(&mut c, (0, 0), &brush('a'), BlendMode::Overwrite);
+        assert!(h.undo(&mut c));
+        assert!(!h.undo(&mut c));
+        assert_eq!(c.row(0)[0], brush('#'));
+    }
+
+    #[test]
+    fn test_history_memory_limit() {
+        let step = 2 * std::mem::size_of::<Character>();
+        let mut c = canvas("....");
+        let mut h = History::new(2 * step);
+
+        for x in 0..4 {
+            h.draw_character(&mut c, (x, 0), &brush('#'), BlendMode::Overwrite);
+        }
+        assert_eq!(h.memory(), 2 * step);
+
+        assert!(h.undo(&mut c));
+        assert!(h.undo(&mut c));
+        assert!(!h.undo(&mut c));
+        assert_eq!(render(&c), "##..");
+
+        h.set_memory_limit(step);
+        assert_eq!(h.memory(), step);
+        assert!(h.redo(&mut c));
+        assert!(!h.redo(&mut c));
+    }
+}
@@ -0,0 +1,340 @@
+use super::{canvas::Canvas, character::{BlendMode, Character}, rect::Rect};
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum LineGlyphs {
+    Brush,
+    Unicode,
+    Ascii
+}
+
+impl LineGlyphs {
+    fn glyph(&self, step: (isize, isize)) -> Option<char> {
+        let (horizontal, vertical, rising, falling) = match self {
+            LineGlyphs::Brush => return None,
+            LineGlyphs::Unicode => ('─', '│', '╱', '╲'),
+            LineGlyphs::Ascii => ('-', '|', '/', '\\')
+        };
+
+        Some(match step {
+            (_, 0) => horizontal,
+            (0, _) => vertical,
+            (x, y) if x * y > 0 => falling,
+            _ => rising
+        })
+    }
+}
+
+
+// The offsets from `start`, going forward or backward, that land between `low` and `high`.
+fn axis_offsets(start: usize, forward: bool, low: usize, high: usize) -> Option<(usize, usize)> {
+    if forward {
+        (start <= high).then(|| (low.saturating_sub(start), high - start))
+    } else {
+        (start >= low).then(|| (start.saturating_sub(high), start - low))
+    }
+}
+
+// A Bresenham line that can tell where any of its steps lands, so clipping never walks the parts off the canvas.
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub(crate) struct LinePath {
+    from: (usize, usize),
+    forward: (bool, bool),
+    delta: (usize, usize)
+}
+
+impl LinePath {
+    pub(crate) fn new(from: (usize, usize), to: (usize, usize)) -> Self {
+        Self {
+            from,
+            forward: (from.0 < to.0, from.1 < to.1),
+            delta: (from.0.abs_diff(to.0), from.1.abs_diff(to.1))
+        }
+    }
+
+    // Every step moves one cell along the longer axis.
+    pub(crate) fn steps(&self) -> usize {
+        self.delta.0.max(self.delta.1)
+    }
+
+    fn x_major(&self) -> bool {
+        self.delta.0 >= self.delta.1
+    }
+
+    fn minor(&self, step: usize) -> usize {
+        let major = self.steps() as u128;
+        if major == 0 {
+            return 0;
+        }
+
+        let progress = step as u128 * self.delta.0.min(self.delta.1) as u128;
+        // Halfway points round up, the same as stepping with an error term does.
+        (progress / major + (2 * (progress % major) >= major) as u128) as usize
+    }
+
+    fn offsets(&self, step: usize) -> (usize, usize) {
+        if self.x_major() {
+            (step, self.minor(step))
+        } else {
+            (self.minor(step), step)
+        }
+    }
+
+    pub(crate) fn point(&self, step: usize) -> (usize, usize) {
+        let (x, y) = self.offsets(step);
+        (
+            if self.forward.0 { self.from.0 + x } else { self.from.0 - x },
+            if self.forward.1 { self.from.1 + y } else { self.from.1 - y }
+        )
+    }
+
+    // The move from `step` to the step after it.
+    fn direction(&self, step: usize) -> (isize, isize) {
+        let (a, b) = (self.offsets(step), self.offsets(step + 1));
+        let sign = |forward: bool| if forward { 1 } else { -1 };
+        ((b.0 - a.0) as isize * sign(self.forward.0), (b.1 - a.1) as isize * sign(self.forward.1))
+    }
+
+    fn first_reaching(&self, offset: usize) -> Option<usize> {
+        if self.minor(self.steps()) < offset {
+            return None;
+        }
+
+        let (mut low, mut high) = (0, self.steps());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self.minor(middle) >= offset {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+        Some(low)
+    }
+
+    // The first and last step inside of `bounds`, both axes only ever move one way so the steps in between are too.
+    pub(crate) fn clip(&self, bounds: Rect) -> Option<(usize, usize)> {
+        if bounds.is_empty() {
+            return None;
+        }
+
+        let x = axis_offsets(self.from.0, self.forward.0, bounds.x, bounds.right() - 1)?;
+        let y = axis_offsets(self.from.1, self.forward.1, bounds.y, bounds.bottom() - 1)?;
+        let (major, minor) = if self.x_major() { (x, y) } else { (y, x) };
+
+        let first = major.0.max(self.first_reaching(minor.0)?);
+        let last = match minor.1.checked_add(1).and_then(|o| self.first_reaching(o)) {
+            Some(s) => s - 1,
+            None => self.steps()
+        };
+        let last = last.min(major.1).min(self.steps());
+
+        (first <= last).then_some((first, last))
+    }
+}
+
+impl Canvas {
+    pub fn draw_line(
+        &mut self,
+        from: (usize, usize),
+        to: (usize, usize),
+        brush: &Character,
+        mode: BlendMode,
+        glyphs: LineGlyphs
+    ) {
+        let path = LinePath::new(from, to);
+        let Some((first, last)) = path.clip(self.bounds()) else {
+            return;
+        };
+
+        for step in first..=last {
+            let direction = if step > 0 {
+                Some(path.direction(step - 1))
+            } else {
+                (path.steps() > 0).then(|| path.direction(0))
+            };
+
+            let character = match direction.and_then(|d| glyphs.glyph(d)) {
+                Some(glyph) => Character::new(glyph, *brush.style()),
+                None => brush.clone()
+            };
+
+            self.draw_character(path.point(step), &character, mode);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{style::{Style, Color, Modifiers}, test_helpers::render};
+
+    use super::*;
+
+    fn points(from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
+        let path = LinePath::new(from, to);
+        (0..=path.steps()).map(|s| path.point(s)).collect()
+    }
+
+    #[test]
+    fn test_line_path_horizontal() {
+        assert_eq!(
+            points((3, 1), (0, 1)),
+            vec![(3, 1), (2, 1), (1, 1), (0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_line_path_shallow() {
+        assert_eq!(
+            points((0, 0), (4, 2)),
+            vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]
+        );
+        assert_eq!(
+            points((1, 4), (0, 0)),
+            vec![(1, 4), (1, 3), (0, 2), (0, 1), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_line_path_single() {
+        assert_eq!(
+            points((2, 2), (2, 2)),
+            vec![(2, 2)]
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_line_brush() {
+        let brush = Character::new('#', Style::new(Color::Red, Color::Black, Modifiers::new(true, false, false)));
+        let mut c = Canvas::new(4, 3);
+
+        c.draw_line((0, 0), (3, 2), &brush, BlendMode::Overwrite, LineGlyphs::Brush);
+
+        assert_eq!(c.row(0)[0], brush);
+        assert_eq!(
+            render(&c),
+            "#   \n ## \n   #"
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_line_unicode() {
+        let brush = Character::new('#', Style::new(Color::Red, Color::Black, Modifiers::new(true, false, false)));
+        let mut c = Canvas::new(5, 5);
+
+        c.draw_line((0, 0), (4, 0), &brush, BlendMode::Overwrite, LineGlyphs::Unicode);
+        c.draw_line((0, 1), (0, 4), &brush, BlendMode::Overwrite, LineGlyphs::Unicode);
+        c.draw_line((1, 1), (3, 3), &brush, BlendMode::Overwrite, LineGlyphs::Unicode);
+        c.draw_line((4, 2), (3, 3), &brush, BlendMode::Overwrite, LineGlyphs::Unicode);
+
+        assert_eq!(c.row(0)[0], Character::new('─', *brush.style()));
+        assert_eq!(
+            render(&c),
+            "─────\n│╲   \n│ ╲ ╱\n│  ╱ \n│    "
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_line_ascii() {
+        let brush = Character::default();
+        let mut c = Canvas::new(4, 2);
+
+        c.draw_line((0, 1), (3, 0), &brush, BlendMode::Overwrite, LineGlyphs::Ascii);
+
+        assert_eq!(
+            render(&c),
+            "  /-\n--  "
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_line_clips() {
+        let brush = Character::new('#', Style::default());
+        let mut c = Canvas::new(3, 2);
+
+        c.draw_line((1, 0), (10, 0), &brush, BlendMode::Overwrite, LineGlyphs::Brush);
+        c.draw_line((5, 5), (10, 10), &brush, BlendMode::Overwrite, LineGlyphs::Brush);
+
+        assert_eq!(
+            render(&c),
+            " ##\n   "
+        );
+    }
+
+    #[test]
+    fn test_line_path_clip() {
+        let bounds = Rect::new(0, 0, 10, 5);
+
+        assert_eq!(LinePath::new((1, 1), (8, 3)).clip(bounds), Some((0, 7)));
+        assert_eq!(LinePath::new((0, 2), (20, 2)).clip(bounds), Some((0, 9)));
+        assert_eq!(LinePath::new((20, 10), (0, 0)).clip(bounds), Some((11, 20)));
+        assert_eq!(LinePath::new((12, 0), (12, 4)).clip(bounds), None);
+        assert_eq!(LinePath::new((0, 0), (0, 0)).clip(Rect::new(0, 0, 0, 0)), None);
+        assert_eq!(LinePath::new((0, 0), (usize::MAX, 1)).clip(bounds), Some((0, 9)));
+    }
+
+    #[test]
+    fn test_canvas_draw_line_clipped_matches_unclipped() {
+        let brush = Character::default();
+        let ends = [(0, 0), (26, 18), (1, 4), (13, 2), (5, 15), (30, 7), (8, 8)];
+
+        for from in ends {
+            for to in ends {
+                let mut small = Canvas::new(11, 7);
+                let mut large = Canvas::new(40, 40);
+                small.draw_line(from, to, &brush, BlendMode::Overwrite, LineGlyphs::Unicode);
+                large.draw_line(from, to, &brush, BlendMode::Overwrite, LineGlyphs::Unicode);
+
+                assert_eq!(small.to_buffer(), large.crop(small.bounds()).to_buffer(), "{from:?} -> {to:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_canvas_draw_line_huge() {
+        let brush = Character::default();
+        let mut c = Canvas::new(4, 2);
+
+        c.draw_line((0, 0), (usize::MAX, 0), &brush, BlendMode::Overwrite, LineGlyphs::Ascii);
+        c.draw_line((usize::MAX, usize::MAX), (0, usize::MAX), &brush, BlendMode::Overwrite, LineGlyphs::Ascii);
+        c.draw_line((3, 1), (usize::MAX, 1), &brush, BlendMode::Overwrite, LineGlyphs::Unicode);
+
+        assert_eq!(
+            render(&c),
+            "----\n   ─"
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_line_wide() {
+        let mut c = Canvas::new(5, 2);
+
+        c.draw_line((0, 0), (4, 0), &Character::new('漢', Style::default()), BlendMode::Overwrite, LineGlyphs::Brush);
+
+        assert_eq!(
+            render(&c),
+            "漢漢 \n     "
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_line_only_character() {
+        let fill = Character::new('_', Style::new(Color::Red, Color::Black, Modifiers::new(false, false, false)));
+        let brush = Character::new('#', Style::new(Color::DarkCyan, Color::Red, Modifiers::new(true, true, false)));
+        let mut c = Canvas::new_filled(3, 1, fill.clone());
+
+        c.draw_line((0, 0), (2, 0), &brush, BlendMode::OnlyCharacter, LineGlyphs::Unicode);
+
+        assert_eq!(
+            c.to_buffer(),
+            vec![vec![Character::new('─', *fill.style()); 3]]
+        );
+    }
+}
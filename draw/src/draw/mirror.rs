@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+
+use super::{box_drawing, canvas::{self, Canvas}, rect::Rect};
+
+const HORIZONTAL_PAIRS: [(char, char); 25] = [
+    ('/', '\\'), ('(', ')'), ('<', '>'), ('[', ']'), ('{', '}'),
+    ('‹', '›'), ('«', '»'), ('⟨', '⟩'),
+    ('╱', '╲'), ('╭', '╮'), ('╰', '╯'),
+    ('▌', '▐'), ('▏', '▕'), ('▖', '▗'), ('▘', '▝'), ('▙', '▟'), ('▛', '▜'), ('▚', '▞'),
+    ('◢', '◣'), ('◤', '◥'), ('◀', '▶'), ('◁', '▷'), ('←', '→'), ('◐', '◑'), ('⌐', '¬')
+];
+
+const VERTICAL_PAIRS: [(char, char); 18] = [
+    ('/', '\\'), ('∧', '∨'), ('‾', '_'),
+    ('╱', '╲'), ('╭', '╰'), ('╮', '╯'),
+    ('▀', '▄'), ('▔', '▁'), ('▖', '▘'), ('▗', '▝'), ('▙', '▛'), ('▟', '▜'), ('▚', '▞'),
+    ('◢', '◥'), ('◣', '◤'), ('▲', '▼'), ('△', '▽'), ('↑', '↓')
+];
+
+// Letters that look mirrored, left out of the defaults so flipping text doesn't change its words.
+const HORIZONTAL_LETTERS: [(char, char); 2] = [('d', 'b'), ('p', 'q')];
+const VERTICAL_LETTERS: [(char, char); 4] = [('^', 'v'), ('b', 'p'), ('d', 'q'), ('M', 'W')];
+
+#[derive(Debug, Clone)]
+pub struct MirrorTable {
+    horizontal: HashMap<char, char>,
+    vertical: HashMap<char, char>
+}
+
+impl MirrorTable {
+    pub fn new() -> Self {
+        Self {
+            horizontal: HashMap::new(),
+            vertical: HashMap::new()
+        }
+    }
+
+    pub fn insert_horizontal(&mut self, a: char, b: char) {
+        self.horizontal.insert(a, b);
+        self.horizontal.insert(b, a);
+    }
+
+    pub fn insert_vertical(&mut self, a: char, b: char) {
+        self.vertical.insert(a, b);
+        self.vertical.insert(b, a);
+    }
+
+    pub fn insert_letters(&mut self) {
+        for (a, b) in HORIZONTAL_LETTERS {
+            self.insert_horizontal(a, b);
+        }
+        for (a, b) in VERTICAL_LETTERS {
+            self.insert_vertical(a, b);
+        }
+    }
+
+    pub fn mirror_horizontal(&self, c: char) -> char {
+        match self.horizontal.get(&c) {
+            Some(m) => *m,
+            None => mirror_box_drawing(c, |[up, right, down, left]| [up, left, down, right])
+        }
+    }
+
+    pub fn mirror_vertical(&self, c: char) -> char {
+        match self.vertical.get(&c) {
+            Some(m) => *m,
+            None => mirror_box_drawing(c, |[up, right, down, left]| [down, right, up, left])
+        }
+    }
+}
+
+impl Default for MirrorTable {
+    fn default() -> Self {
+        let mut table = Self::new();
+        for (a, b) in HORIZONTAL_PAIRS {
+            table.insert_horizontal(a, b);
+        }
+        for (a, b) in VERTICAL_PAIRS {
+            table.insert_vertical(a, b);
+        }
+        table
+    }
+}
+
+fn mirror_box_drawing(c: char, mirror: fn([box_drawing::Weight; 4]) -> [box_drawing::Weight; 4]) -> char {
+    match box_drawing::arms(c) {
+        Some(arms) if mirror(arms) != arms => box_drawing::glyph(mirror(arms)).unwrap_or(c),
+        _ => c
+    }
+}
+
+
+impl Canvas {
+    pub fn flip_horizontal(&mut self, table: &MirrorTable) {
+        self.flip_region_horizontal(self.bounds(), table);
+    }
+
+    pub fn flip_vertical(&mut self, table: &MirrorTable) {
+        self.flip_region_vertical(self.bounds(), table);
+    }
+
+    pub fn flip_region_horizontal(&mut self, region: Rect, table: &MirrorTable) {
+        let region = region.intersection(&self.bounds());
+        for y in region.y..region.bottom() {
+            self.split_wide_at((region.x, y));
+            self.split_wide_at((region.right(), y));
+            let l = &mut self.row_mut(y)[region.x..region.right()];
+            l.reverse();
+            for c in l.iter_mut() {
+                c.set_character(table.mirror_horizontal(c.character()));
+            }
+            canvas::swap_reversed_wide(l);
+        }
+    }
+
+    pub fn flip_region_vertical(&mut self, region: Rect, table: &MirrorTable) {
+        let region = region.intersection(&self.bounds());
+        for y in region.y..region.bottom() {
+            self.split_wide_at((region.x, y));
+            self.split_wide_at((region.right(), y));
+        }
+        let width = self.width();
+        let cells = self.cells_mut();
+        for y in 0..region.height / 2 {
+            let (top, bottom) = (region.y + y, region.bottom() - 1 - y);
+            let (upper, lower) = cells.split_at_mut(bottom * width);
+            upper[top * width + region.x..top * width + region.right()].swap_with_slice(&mut lower[region.x..region.right()]);
+        }
+        for y in region.y..region.bottom() {
+            for c in self.row_mut(y)[region.x..region.right()].iter_mut() {
+                c.set_character(table.mirror_vertical(c.character()));
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{character::Character, style::{Style, Color, Modifiers}, test_helpers::{render, canvas}};
+
+    use super::*;
+
+    #[test]
+    fn test_mirror_table_default() {
+        let table = MirrorTable::default();
+
+        assert_eq!(table.mirror_horizontal('/'), '\\');
+        assert_eq!(table.mirror_horizontal('\\'), '/');
+        assert_eq!(table.mirror_horizontal('┌'), '┐');
+        assert_eq!(table.mirror_horizontal('├'), '┤');
+        assert_eq!(table.mirror_horizontal('╞'), '╡');
+        assert_eq!(table.mirror_horizontal('╭'), '╮');
+        assert_eq!(table.mirror_horizontal('┄'), '┄');
+        assert_eq!(table.mirror_horizontal('▀'), '▀');
+        assert_eq!(table.mirror_horizontal('a'), 'a');
+
+        assert_eq!(table.mirror_vertical('▀'), '▄');
+        assert_eq!(table.mirror_vertical('┌'), '└');
+        assert_eq!(table.mirror_vertical('┬'), '┴');
+        assert_eq!(table.mirror_vertical('╭'), '╰');
+        assert_eq!(table.mirror_vertical('│'), '│');
+    }
+
+    #[test]
+    fn test_mirror_table_custom() {
+        let mut table = MirrorTable::new();
+        table.insert_horizontal('a', 'z');
+
+        assert_eq!(table.mirror_horizontal('z'), 'a');
+        assert_eq!(table.mirror_horizontal('/'), '/');
+        assert_eq!(table.mirror_horizontal('┌'), '┐');
+    }
+
+    #[test]
+    fn test_canvas_flip_horizontal() {
+        let mut c = canvas("┌─╮ /\n│<┤ (");
+
+        c.flip_horizontal(&MirrorTable::default());
+
+        assert_eq!(
+            render(&c),
+            "\\ ╭─┐\n) ├>│"
+        );
+    }
+
+    #[test]
+    fn test_canvas_flip_horizontal_keeps_style() {
+        let style = Style::new(Color::Red, Color::Black, Modifiers::new(true, false, false));
+        let mut c = Canvas::new_with_buffer(vec![vec![Character::new('▌', style), Character::default()]]);
+
+        c.flip_horizontal(&MirrorTable::default());
+
+        assert_eq!(
+            c.to_buffer(),
+            vec![vec![Character::default(), Character::new('▐', style)]]
+        );
+    }
+
+    #[test]
+    fn test_canvas_flip_horizontal_wide() {
+        let mut c = canvas("a漢c");
+
+        c.flip_horizontal(&MirrorTable::default());
+
+        assert_eq!(render(&c), "c漢a");
+        assert!(c.row(0)[2].is_continuation());
+    }
+
+    #[test]
+    fn test_canvas_flip_region_splits_wide() {
+        let mut c = canvas("漢ab字");
+
+        c.flip_region_horizontal(Rect::new(1, 0, 4, 1), &MirrorTable::new());
+        assert_eq!(render(&c), "  ba  ");
+        assert!(!c.row(0).iter().any(Character::is_continuation));
+
+        let mut c = canvas("a漢\nbcd");
+        c.flip_region_vertical(Rect::new(0, 0, 2, 2), &MirrorTable::new());
+        assert_eq!(render(&c), "bc \na d");
+        assert!(!c.row(1)[1].is_continuation());
+    }
+
+    #[test]
+    fn test_canvas_flip_vertical() {
+        let mut c = canvas("┌┬▀\n│ /\n└┴v");
+
+        c.flip_vertical(&MirrorTable::default());
+
+        assert_eq!(
+            render(&c),
+            "┌┬v\n│ \\\n└┴▄"
+        );
+    }
+
+    #[test]
+    fn test_mirror_table_letters() {
+        let mut c = canvas("bad\nMvp");
+        c.flip_horizontal(&MirrorTable::default());
+        c.flip_vertical(&MirrorTable::default());
+        assert_eq!(render(&c), "pvM\ndab");
+
+        let mut table = MirrorTable::default();
+        table.insert_letters();
+        assert_eq!(table.mirror_horizontal('d'), 'b');
+        assert_eq!(table.mirror_vertical('v'), '^');
+        assert_eq!(table.mirror_vertical('M'), 'W');
+        assert_eq!(table.mirror_vertical('/'), '\\');
+    }
+
+    #[test]
+    fn test_canvas_flip_region() {
+        let mut c = canvas("abcd\nefgh\nijkl");
+
+        c.flip_region_horizontal(Rect::new(1, 0, 2, 2), &MirrorTable::new());
+        assert_eq!(
+            render(&c),
+            "acbd\negfh\nijkl"
+        );
+
+        c.flip_region_vertical(Rect::new(2, 1, 5, 5), &MirrorTable::new());
+        assert_eq!(
+            render(&c),
+            "acbd\negkl\nijfh"
+        );
+    }
+}
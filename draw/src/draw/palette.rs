@@ -0,0 +1,361 @@
+use std::fmt::Display;
+
+use super::{quantize::QuantizeTarget, style::Color};
+
+const VGA: [(&str, [u8; 3]); 16] = [
+    ("Black", [0, 0, 0]), ("Blue", [0, 0, 170]), ("Green", [0, 170, 0]), ("Cyan", [0, 170, 170]),
+    ("Red", [170, 0, 0]), ("Magenta", [170, 0, 170]), ("Brown", [170, 85, 0]), ("Light Gray", [170, 170, 170]),
+    ("Dark Gray", [85, 85, 85]), ("Light Blue", [85, 85, 255]), ("Light Green", [85, 255, 85]), ("Light Cyan", [85, 255, 255]),
+    ("Light Red", [255, 85, 85]), ("Light Magenta", [255, 85, 255]), ("Yellow", [255, 255, 85]), ("White", [255, 255, 255])
+];
+
+const SOLARIZED: [(&str, [u8; 3]); 16] = [
+    ("base03", [0, 43, 54]), ("base02", [7, 54, 66]), ("base01", [88, 110, 117]), ("base00", [101, 123, 131]),
+    ("base0", [131, 148, 150]), ("base1", [147, 161, 161]), ("base2", [238, 232, 213]), ("base3", [253, 246, 227]),
+    ("yellow", [181, 137, 0]), ("orange", [203, 75, 22]), ("red", [220, 50, 47]), ("magenta", [211, 54, 130]),
+    ("violet", [108, 113, 196]), ("blue", [38, 139, 210]), ("cyan", [42, 161, 152]), ("green", [133, 153, 0])
+];
+
+pub const BUILTIN: [&str; 4] = ["ansi", "vga", "xterm-256", "solarized"];
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq
+)]
+pub enum PaletteError {
+    InvalidHeader,
+    InvalidLine { line: usize }
+}
+
+impl Display for PaletteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaletteError::InvalidHeader => write!(f, "Invalid palette header"),
+            PaletteError::InvalidLine { line } => write!(f, "Invalid palette entry on line {}", line)
+        }
+    }
+}
+
+impl std::error::Error for PaletteError {}
+
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq
+)]
+pub struct Swatch {
+    pub color: Color,
+    pub name: Option<String>
+}
+
+impl Swatch {
+    pub fn new(color: Color, name: Option<String>) -> Self {
+        Self {
+            color,
+            name
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq
+)]
+pub struct Palette {
+    name: String,
+    swatches: Vec<Swatch>
+}
+
+fn rgb_palette(name: &str, colors: &[(&str, [u8; 3])]) -> Palette {
+    let mut palette = Palette::new(name);
+    for (n, [r, g, b]) in colors {
+        palette.push(Color::Rgb { r: *r, g: *g, b: *b }, Some(n.to_string()));
+    }
+    palette
+}
+
+// Lines with their 1-based numbers, skipping blank ones and those starting with `comment`.
+fn entries<'a>(data: &'a str, comment: &'a str) -> impl Iterator<Item = (usize, &'a str)> + 'a {
+    data.lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(move |(_, l)| !l.is_empty() && !l.starts_with(comment))
+}
+
+impl Palette {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            swatches: Vec::new()
+        }
+    }
+
+    pub fn from_colors(name: &str, colors: &[Color]) -> Self {
+        let mut palette = Self::new(name);
+        for c in colors {
+            palette.push(*c, None);
+        }
+        palette
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_string();
+    }
+
+    pub fn swatches(&self) -> &[Swatch] {
+        &self.swatches
+    }
+
+    pub fn swatches_mut(&mut self) -> &mut Vec<Swatch> {
+        &mut self.swatches
+    }
+
+    pub fn colors(&self) -> Vec<Color> {
+        self.swatches.iter().map(|s| s.color).collect()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Swatch> {
+        self.swatches.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.swatches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.swatches.is_empty()
+    }
+
+    pub fn push(&mut self, color: Color, name: Option<String>) {
+        self.swatches.push(Swatch::new(color, name));
+    }
+
+    pub fn position(&self, color: Color) -> Option<usize> {
+        self.swatches.iter().position(|s| s.color == color)
+    }
+
+    pub fn nearest(&self, color: Color) -> Option<Color> {
+        color.nearest(&self.colors())
+    }
+
+    pub fn builtin(name: &str) -> Option<Palette> {
+        match name {
+            "ansi" => Some(Self::ansi()),
+            "vga" => Some(Self::vga()),
+            "xterm-256" => Some(Self::xterm256()),
+            "solarized" => Some(Self::solarized()),
+            _ => None
+        }
+    }
+
+    pub fn ansi() -> Self {
+        let mut palette = Self::new("ansi");
+        for c in Color::NAMED {
+            palette.push(c, Some(c.to_string()));
+        }
+        palette
+    }
+
+    pub fn vga() -> Self {
+        rgb_palette("vga", &VGA)
+    }
+
+    pub fn xterm256() -> Self {
+        Self::from_colors("xterm-256", &(0..=255).map(|i| Color::Indexed { i }).collect::<Vec<_>>())
+    }
+
+    pub fn solarized() -> Self {
+        rgb_palette("solarized", &SOLARIZED)
+    }
+
+    pub fn from_gpl(data: &str) -> Result<Self, PaletteError> {
+        let mut lines = entries(data, "#");
+        if lines.next().map(|(_, l)| l) != Some("GIMP Palette") {
+            return Err(PaletteError::InvalidHeader);
+        }
+
+        let mut palette = Self::new("");
+        for (line, l) in lines {
+            if let Some(name) = l.strip_prefix("Name:") {
+                palette.set_name(name.trim());
+                continue;
+            }
+            if l.starts_with("Columns:") {
+                continue;
+            }
+
+            let mut fields = l.split_whitespace();
+            let mut channel = || fields.next().and_then(|v| v.parse().ok()).ok_or(PaletteError::InvalidLine { line });
+            let (r, g, b) = (channel()?, channel()?, channel()?);
+            let name = fields.collect::<Vec<_>>().join(" ");
+            palette.push(Color::Rgb { r, g, b }, Some(name).filter(|n| !n.is_empty()));
+        }
+        Ok(palette)
+    }
+
+    // Colours without an RGB value, like `None`, are left out of every export.
+    pub fn to_gpl(&self) -> String {
+        let mut gpl = format!("GIMP Palette\nName: {}\n#\n", self.name);
+        for (s, [r, g, b]) in self.rgb_swatches() {
+            gpl += &format!("{:3} {:3} {:3}", r, g, b);
+            if let Some(name) = &s.name {
+                gpl += &format!("\t{}", name);
+            }
+            gpl += "\n";
+        }
+        gpl
+    }
+
+    pub fn from_paint_net(data: &str) -> Result<Self, PaletteError> {
+        // The name is kept in a comment on the first line, the way `to_paint_net` writes it.
+        let name = data.lines().next().and_then(|l| l.trim().strip_prefix(';')).unwrap_or("");
+        let mut palette = Self::new(name.trim());
+        for (line, l) in entries(data, ";") {
+            // Entries are AARRGGBB, the alpha is dropped.
+            let color = l.get(2..)
+                .filter(|_| l.len() == 8)
+                .and_then(|rgb| format!("#{}", rgb).parse().ok())
+                .ok_or(PaletteError::InvalidLine { line })?;
+            palette.push(color, None);
+        }
+        Ok(palette)
+    }
+
+    pub fn to_paint_net(&self) -> String {
+        let mut txt = if self.name.is_empty() { String::new() } else { format!("; {}\n", self.name) };
+        for (_, [r, g, b]) in self.rgb_swatches() {
+            txt += &format!("FF{:02X}{:02X}{:02X}\n", r, g, b);
+        }
+        txt
+    }
+
+    pub fn from_hex(data: &str) -> Result<Self, PaletteError> {
+        let mut palette = Self::new("");
+        for (line, l) in entries(data, "//") {
+            let color = format!("#{}", l.trim_start_matches('#')).parse().map_err(|_| PaletteError::InvalidLine { line })?;
+            palette.push(color, None);
+        }
+        Ok(palette)
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.rgb_swatches()
+            .map(|(_, [r, g, b])| format!("#{:02x}{:02x}{:02x}\n", r, g, b))
+            .collect()
+    }
+
+    fn rgb_swatches(&self) -> impl Iterator<Item = (&Swatch, [u8; 3])> + '_ {
+        self.swatches.iter().filter_map(|s| s.color.to_rgb().map(|rgb| (s, rgb)))
+    }
+}
+
+impl From<&Palette> for QuantizeTarget {
+    fn from(palette: &Palette) -> Self {
+        QuantizeTarget::Custom(palette.colors())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_builtin() {
+        for name in BUILTIN {
+            let palette = Palette::builtin(name).unwrap();
+            assert_eq!(palette.name(), name);
+            assert!(!palette.is_empty());
+        }
+        assert!(Palette::builtin("missing").is_none());
+
+        assert_eq!(Palette::xterm256().len(), 256);
+        assert_eq!(Palette::vga().get(6).unwrap().name.as_deref(), Some("Brown"));
+        assert_eq!(Palette::ansi().get(1).unwrap(), &Swatch::new(Color::DarkRed, Some("dark_red".to_string())));
+        assert_eq!(Palette::solarized().get(0).unwrap().color, Color::Rgb { r: 0, g: 43, b: 54 });
+    }
+
+    #[test]
+    fn test_palette_nearest() {
+        let palette = Palette::vga();
+
+        assert_eq!(palette.nearest(Color::Rgb { r: 160, g: 90, b: 10 }), Some(Color::Rgb { r: 170, g: 85, b: 0 }));
+        assert_eq!(palette.position(Color::Rgb { r: 255, g: 255, b: 255 }), Some(15));
+        assert_eq!(Palette::new("empty").nearest(Color::Red), None);
+        assert_eq!(QuantizeTarget::from(&palette), QuantizeTarget::Custom(palette.colors()));
+    }
+
+    #[test]
+    fn test_palette_gpl() {
+        let gpl = "GIMP Palette\nName: Test\nColumns: 4\n# comment\n255   0   0\tBright Red\n  0 128 255\n";
+        let palette = Palette::from_gpl(gpl).unwrap();
+
+        assert_eq!(palette.name(), "Test");
+        assert_eq!(
+            palette.swatches(),
+            &[
+                Swatch::new(Color::Rgb { r: 255, g: 0, b: 0 }, Some("Bright Red".to_string())),
+                Swatch::new(Color::Rgb { r: 0, g: 128, b: 255 }, None)
+            ]
+        );
+        assert_eq!(palette.to_gpl(), "GIMP Palette\nName: Test\n#\n255   0   0\tBright Red\n  0 128 255\n");
+        assert_eq!(Palette::from_gpl(&palette.to_gpl()).unwrap(), palette);
+    }
+
+    #[test]
+    fn test_palette_gpl_round_trip() {
+        let palette = Palette::solarized();
+
+        assert_eq!(Palette::from_gpl(&palette.to_gpl()).unwrap(), palette);
+    }
+
+    #[test]
+    fn test_palette_gpl_errors() {
+        assert_eq!(Palette::from_gpl("JASC-PAL\n"), Err(PaletteError::InvalidHeader));
+        assert_eq!(Palette::from_gpl("GIMP Palette\n1 2 3\n1 2\n"), Err(PaletteError::InvalidLine { line: 3 }));
+        assert_eq!(Palette::from_gpl("GIMP Palette\n1 2 300\n").unwrap_err().to_string(), "Invalid palette entry on line 2");
+    }
+
+    #[test]
+    fn test_palette_paint_net() {
+        let palette = Palette::from_paint_net("; paint.net palette\nFFFF0000\n8000ff80\n").unwrap();
+
+        assert_eq!(palette.colors(), vec![Color::Rgb { r: 255, g: 0, b: 0 }, Color::Rgb { r: 0, g: 255, b: 128 }]);
+        assert_eq!(palette.name(), "paint.net palette");
+        assert_eq!(palette.to_paint_net(), "; paint.net palette\nFFFF0000\nFF00FF80\n");
+        assert_eq!(Palette::from_paint_net("FFFF0000\n; not a name\n").unwrap().name(), "");
+        assert_eq!(Palette::from_paint_net("FF0000\n"), Err(PaletteError::InvalidLine { line: 1 }));
+
+        let vga = Palette::vga();
+        let round_trip = Palette::from_paint_net(&vga.to_paint_net()).unwrap();
+        assert_eq!(round_trip.name(), vga.name());
+        assert_eq!(round_trip.colors(), vga.colors());
+    }
+
+    #[test]
+    fn test_palette_hex() {
+        let palette = Palette::from_hex("#ff0000\n\n00ff00\n// comment\n#00f\n").unwrap();
+
+        assert_eq!(
+            palette.colors(),
+            vec![Color::Rgb { r: 255, g: 0, b: 0 }, Color::Rgb { r: 0, g: 255, b: 0 }, Color::Rgb { r: 0, g: 0, b: 255 }]
+        );
+        assert_eq!(palette.to_hex(), "#ff0000\n#00ff00\n#0000ff\n");
+        assert_eq!(Palette::from_hex("#ff0000\nnope\n"), Err(PaletteError::InvalidLine { line: 2 }));
+    }
+
+    #[test]
+    fn test_palette_export_skips_colors_without_rgb() {
+        let palette = Palette::from_colors("mixed", &[Color::None, Color::DarkGreen, Color::Transparent]);
+
+        assert_eq!(palette.to_hex(), "#008000\n");
+        assert_eq!(palette.to_gpl(), "GIMP Palette\nName: mixed\n#\n  0 128   0\n");
+    }
+}
@@ -0,0 +1,14 @@
+pub mod block;
+pub mod braille;
+
+use super::{ellipse::ellipse_points, line::LinePath, rect::Rect};
+
+pub(crate) fn line_pixels(from: (usize, usize), to: (usize, usize), bounds: Rect) -> impl Iterator<Item = (usize, usize)> {
+    let path = LinePath::new(from, to);
+    path.clip(bounds).into_iter().flat_map(move |(first, last)| (first..=last).map(move |s| path.point(s)))
+}
+
+pub(crate) fn circle_pixels(center: (usize, usize), radius: usize) -> impl Iterator<Item = (usize, usize)> {
+    ellipse_points((radius, radius)).into_iter()
+        .filter_map(move |(x, y)| Some((center.0.checked_add_signed(x)?, center.1.checked_add_signed(y)?)))
+}
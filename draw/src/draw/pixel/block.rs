@@ -0,0 +1,295 @@
+use crate::draw::{canvas::Canvas, character::{BlendMode, Character}, rect::Rect, style::{Color, Modifiers, Style}};
+
+use super::{circle_pixels, line_pixels};
+
+// Quadrant glyphs indexed by pattern, bits are top left, top right, bottom left, bottom right.
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█'
+];
+
+const SEXTANT_START: u32 = 0x1FB00;
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum BlockMode {
+    HalfBlock,
+    Quadrant,
+    Sextant
+}
+
+impl BlockMode {
+    pub fn cell_size(&self) -> (usize, usize) {
+        match self {
+            BlockMode::HalfBlock => (1, 2),
+            BlockMode::Quadrant => (2, 2),
+            BlockMode::Sextant => (2, 3)
+        }
+    }
+
+    // Pattern bits go row by row, left to right, starting from the top left pixel.
+    fn glyph(&self, pattern: u8) -> char {
+        match self {
+            BlockMode::HalfBlock => [' ', '▀', '▄', '█'][pattern as usize],
+            BlockMode::Quadrant => QUADRANTS[pattern as usize],
+            BlockMode::Sextant => match pattern {
+                0 => ' ',
+                21 => '▌',
+                42 => '▐',
+                63 => '█',
+                // The sextant block skips the patterns that already exist as half blocks.
+                p => {
+                    let skipped = u32::from(p > 21) + u32::from(p > 42);
+                    char::from_u32(SEXTANT_START + p as u32 - 1 - skipped).unwrap_or('█')
+                }
+            }
+        }
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct BlockCanvas {
+    mode: BlockMode,
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>
+}
+
+impl BlockCanvas {
+    pub fn new(mode: BlockMode, width: usize, height: usize) -> Self {
+        let (cell_width, cell_height) = mode.cell_size();
+        Self {
+            mode,
+            width,
+            height,
+            pixels: vec![Color::Transparent; width * cell_width * height * cell_height]
+        }
+    }
+
+    pub fn mode(&self) -> BlockMode {
+        self.mode
+    }
+
+    pub fn width(&self) -> usize {
+        self.width * self.mode.cell_size().0
+    }
+
+    pub fn height(&self) -> usize {
+        self.height * self.mode.cell_size().1
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Option<Color> {
+        if x < self.width() && y < self.height() {
+            Some(self.pixels[y * self.width() + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+        if x < self.width() && y < self.height() {
+            let width = self.width();
+            self.pixels[y * width + x] = color;
+        }
+    }
+
+    pub fn clear_pixel(&mut self, x: usize, y: usize) {
+        self.set_pixel(x, y, Color::Transparent);
+    }
+
+    pub fn clear(&mut self) {
+        self.pixels.fill(Color::Transparent);
+    }
+
+    pub fn draw_line(&mut self, from: (usize, usize), to: (usize, usize), color: Color) {
+        for (x, y) in line_pixels(from, to, Rect::new(0, 0, self.width(), self.height())) {
+            self.set_pixel(x, y, color);
+        }
+    }
+
+    pub fn draw_circle(&mut self, center: (usize, usize), radius: usize, color: Color) {
+        for (x, y) in circle_pixels(center, radius) {
+            self.set_pixel(x, y, color);
+        }
+    }
+
+    // A cell shows at most two colours, any further colours are merged into the most common one.
+    pub fn cell(&self, x: usize, y: usize) -> Option<Character> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let (cell_width, cell_height) = self.mode.cell_size();
+        let colors: Vec<Color> = (0..cell_height).flat_map(|py| {
+            (0..cell_width).map(move |px| (x * cell_width + px, y * cell_height + py))
+        }).map(|(px, py)| self.pixels[py * self.width() + px]).collect();
+
+        let mut counts: Vec<(Color, usize)> = Vec::new();
+        for c in colors.iter() {
+            match counts.iter_mut().find(|(k, _)| k == c) {
+                Some(count) => count.1 += 1,
+                None => counts.push((*c, 1))
+            }
+        }
+        counts.sort_by_key(|c| std::cmp::Reverse(c.1));
+
+        let first = counts[0].0;
+        let second = counts.get(1).map_or(Color::Transparent, |c| c.0);
+        let (fg, bg) = if first.is_transparent() { (second, first) } else { (first, second) };
+        if fg.is_transparent() {
+            return None;
+        }
+
+        let pattern = colors.iter().enumerate()
+            .filter(|(_, c)| **c != bg)
+            .fold(0, |pattern, (i, _)| pattern | 1 << i);
+
+        Some(Character::new(self.mode.glyph(pattern), Style::new(fg, bg, Modifiers::default())))
+    }
+
+    // Cells without any pixels set are left untouched, a transparent background keeps the canvas one.
+    pub fn render(&self, canvas: &mut Canvas, at: (usize, usize), mode: BlendMode) {
+        for y in 0..self.height {
+            let Some(cy) = at.1.checked_add(y) else {
+                break;
+            };
+            for x in 0..self.width {
+                let Some(cx) = at.0.checked_add(x) else {
+                    break;
+                };
+                if let Some(c) = self.cell(x, y) {
+                    canvas.draw_character((cx, cy), &c, mode);
+                }
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::test_helpers::render;
+
+    use super::*;
+
+    fn style(fg: Color, bg: Color) -> Style {
+        Style::new(fg, bg, Modifiers::default())
+    }
+
+    #[test]
+    fn test_block_size() {
+        assert_eq!(BlockCanvas::new(BlockMode::HalfBlock, 3, 2).width(), 3);
+        assert_eq!(BlockCanvas::new(BlockMode::HalfBlock, 3, 2).height(), 4);
+        assert_eq!(BlockCanvas::new(BlockMode::Quadrant, 3, 2).width(), 6);
+        assert_eq!(BlockCanvas::new(BlockMode::Sextant, 3, 2).height(), 6);
+    }
+
+    #[test]
+    fn test_block_pixels() {
+        let mut b = BlockCanvas::new(BlockMode::Quadrant, 1, 1);
+
+        b.set_pixel(1, 1, Color::Red);
+        b.set_pixel(2, 0, Color::Red);
+        assert_eq!(b.pixel(1, 1), Some(Color::Red));
+        assert_eq!(b.pixel(0, 0), Some(Color::Transparent));
+        assert_eq!(b.pixel(2, 0), None);
+
+        b.clear_pixel(1, 1);
+        assert_eq!(b.pixel(1, 1), Some(Color::Transparent));
+        assert_eq!(b.cell(0, 0), None);
+    }
+
+    #[test]
+    fn test_block_half_block() {
+        let mut b = BlockCanvas::new(BlockMode::HalfBlock, 3, 1);
+        b.set_pixel(0, 0, Color::Red);
+        b.set_pixel(0, 1, Color::Blue);
+        b.set_pixel(1, 1, Color::Green);
+        b.set_pixel(2, 0, Color::Green);
+        b.set_pixel(2, 1, Color::Green);
+
+        assert_eq!(b.cell(0, 0), Some(Character::new('▀', style(Color::Red, Color::Blue))));
+        assert_eq!(b.cell(1, 0), Some(Character::new('▄', style(Color::Green, Color::Transparent))));
+        assert_eq!(b.cell(2, 0), Some(Character::new('█', style(Color::Green, Color::Transparent))));
+    }
+
+    #[test]
+    fn test_block_quadrant() {
+        let mut b = BlockCanvas::new(BlockMode::Quadrant, 2, 1);
+        b.set_pixel(0, 0, Color::Red);
+        b.set_pixel(1, 1, Color::Red);
+        b.set_pixel(1, 0, Color::Blue);
+        b.set_pixel(0, 1, Color::Blue);
+        b.set_pixel(3, 0, Color::Red);
+        b.set_pixel(2, 1, Color::Blue);
+        b.set_pixel(3, 1, Color::Green);
+
+        assert_eq!(b.cell(0, 0), Some(Character::new('▚', style(Color::Red, Color::Blue))));
+        assert_eq!(b.cell(1, 0), Some(Character::new('▟', style(Color::Red, Color::Transparent))));
+    }
+
+    #[test]
+    fn test_block_more_colors() {
+        let mut b = BlockCanvas::new(BlockMode::Quadrant, 1, 1);
+        b.set_pixel(0, 0, Color::Red);
+        b.set_pixel(1, 0, Color::Red);
+        b.set_pixel(0, 1, Color::Blue);
+        b.set_pixel(1, 1, Color::Green);
+
+        assert_eq!(b.cell(0, 0), Some(Character::new('▜', style(Color::Red, Color::Blue))));
+    }
+
+    #[test]
+    fn test_block_sextant_glyphs() {
+        let mode = BlockMode::Sextant;
+
+        assert_eq!(mode.glyph(0), ' ');
+        assert_eq!(mode.glyph(1), '🬀');
+        assert_eq!(mode.glyph(20), '🬓');
+        assert_eq!(mode.glyph(21), '▌');
+        assert_eq!(mode.glyph(22), '🬔');
+        assert_eq!(mode.glyph(42), '▐');
+        assert_eq!(mode.glyph(62), '🬻');
+        assert_eq!(mode.glyph(63), '█');
+    }
+
+    #[test]
+    fn test_block_sextant() {
+        let mut b = BlockCanvas::new(BlockMode::Sextant, 1, 1);
+        b.draw_line((0, 0), (0, 2), Color::Yellow);
+
+        assert_eq!(b.cell(0, 0), Some(Character::new('▌', style(Color::Yellow, Color::Transparent))));
+
+        b.set_pixel(1, 2, Color::Yellow);
+        assert_eq!(b.cell(0, 0), Some(Character::new('🬲', style(Color::Yellow, Color::Transparent))));
+    }
+
+    #[test]
+    fn test_block_render() {
+        let mut c = Canvas::new_filled(3, 2, Character::new('.', style(Color::White, Color::Black)));
+        let mut b = BlockCanvas::new(BlockMode::HalfBlock, 2, 1);
+        b.draw_line((0, 0), (1, 0), Color::Red);
+        b.set_pixel(1, 1, Color::Blue);
+
+        b.render(&mut c, (1, 1), BlendMode::Overwrite);
+
+        assert_eq!(render(&c), "...\n.▀▀");
+        assert_eq!(c.get(1, 1), Some(&Character::new('▀', style(Color::Red, Color::Black))));
+        assert_eq!(c.get(2, 1), Some(&Character::new('▀', style(Color::Red, Color::Blue))));
+    }
+
+    #[test]
+    fn test_block_render_far_away() {
+        let mut c = Canvas::new_filled(2, 1, Character::new('.', style(Color::White, Color::Black)));
+        let mut b = BlockCanvas::new(BlockMode::HalfBlock, 2, 1);
+        b.draw_line((0, 0), (1, 1), Color::Red);
+
+        b.render(&mut c, (usize::MAX, 0), BlendMode::Overwrite);
+        b.render(&mut c, (0, usize::MAX), BlendMode::Overwrite);
+
+        assert_eq!(render(&c), "..");
+    }
+}
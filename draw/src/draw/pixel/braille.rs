@@ -0,0 +1,214 @@
+use crate::draw::{canvas::Canvas, character::{BlendMode, Character}, rect::Rect, style::Style};
+
+use super::{circle_pixels, line_pixels};
+
+const BLANK: u32 = 0x2800;
+
+// Dot bits of a braille cell, indexed by [y][x].
+const DOTS: [[u8; 2]; 4] = [
+    [0x01, 0x08],
+    [0x02, 0x10],
+    [0x04, 0x20],
+    [0x40, 0x80]
+];
+
+#[derive(Debug, Clone)]
+pub struct BrailleCanvas {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>
+}
+
+impl BrailleCanvas {
+    pub const CELL_WIDTH: usize = 2;
+    pub const CELL_HEIGHT: usize = 4;
+
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![0; width * height]
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width * Self::CELL_WIDTH
+    }
+
+    pub fn height(&self) -> usize {
+        self.height * Self::CELL_HEIGHT
+    }
+
+    fn dot(&self, x: usize, y: usize) -> Option<(usize, u8)> {
+        if x >= self.width() || y >= self.height() {
+            return None;
+        }
+        let cell = (y / Self::CELL_HEIGHT) * self.width + x / Self::CELL_WIDTH;
+        Some((cell, DOTS[y % Self::CELL_HEIGHT][x % Self::CELL_WIDTH]))
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        self.dot(x, y).is_some_and(|(cell, bit)| self.cells[cell] & bit != 0)
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize) {
+        if let Some((cell, bit)) = self.dot(x, y) {
+            self.cells[cell] |= bit;
+        }
+    }
+
+    pub fn clear_pixel(&mut self, x: usize, y: usize) {
+        if let Some((cell, bit)) = self.dot(x, y) {
+            self.cells[cell] &= !bit;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.fill(0);
+    }
+
+    pub fn draw_line(&mut self, from: (usize, usize), to: (usize, usize)) {
+        for (x, y) in line_pixels(from, to, Rect::new(0, 0, self.width(), self.height())) {
+            self.set_pixel(x, y);
+        }
+    }
+
+    pub fn draw_circle(&mut self, center: (usize, usize), radius: usize) {
+        for (x, y) in circle_pixels(center, radius) {
+            self.set_pixel(x, y);
+        }
+    }
+
+    pub fn glyph(&self, x: usize, y: usize) -> Option<char> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        char::from_u32(BLANK + self.cells[y * self.width + x] as u32)
+    }
+
+    // Cells without any dots are left untouched.
+    pub fn render(&self, canvas: &mut Canvas, at: (usize, usize), style: Style, mode: BlendMode) {
+        for y in 0..self.height {
+            let Some(cy) = at.1.checked_add(y) else {
+                break;
+            };
+            for x in 0..self.width {
+                let Some(cx) = at.0.checked_add(x) else {
+                    break;
+                };
+                if self.cells[y * self.width + x] == 0 {
+                    continue;
+                }
+                if let Some(glyph) = self.glyph(x, y) {
+                    canvas.draw_character((cx, cy), &Character::new(glyph, style), mode);
+                }
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{style::{Color, Modifiers}, test_helpers::render};
+
+    use super::*;
+
+    #[test]
+    fn test_braille_size() {
+        let b = BrailleCanvas::new(3, 2);
+
+        assert_eq!(b.width(), 6);
+        assert_eq!(b.height(), 8);
+    }
+
+    #[test]
+    fn test_braille_pixels() {
+        let mut b = BrailleCanvas::new(2, 1);
+
+        b.set_pixel(0, 0);
+        assert_eq!(b.glyph(0, 0), Some('⠁'));
+
+        b.set_pixel(1, 3);
+        assert_eq!(b.glyph(0, 0), Some('⢁'));
+        assert!(b.pixel(1, 3));
+
+        b.clear_pixel(0, 0);
+        assert_eq!(b.glyph(0, 0), Some('⢀'));
+        assert!(!b.pixel(0, 0));
+
+        b.set_pixel(2, 1);
+        assert_eq!(b.glyph(1, 0), Some('⠂'));
+
+        b.set_pixel(4, 0);
+        b.set_pixel(0, 4);
+        assert!(!b.pixel(4, 0));
+        assert_eq!(b.glyph(2, 0), None);
+
+        b.clear();
+        assert_eq!(b.glyph(0, 0), Some('⠀'));
+    }
+
+    #[test]
+    fn test_braille_all_dots() {
+        let mut b = BrailleCanvas::new(1, 1);
+        for y in 0..4 {
+            for x in 0..2 {
+                b.set_pixel(x, y);
+            }
+        }
+
+        assert_eq!(b.glyph(0, 0), Some('⣿'));
+    }
+
+    #[test]
+    fn test_braille_line() {
+        let mut b = BrailleCanvas::new(2, 1);
+
+        b.draw_line((0, 0), (3, 3));
+
+        assert_eq!(b.glyph(0, 0), Some('⠑'));
+        assert_eq!(b.glyph(1, 0), Some('⢄'));
+    }
+
+    #[test]
+    fn test_braille_circle() {
+        let mut b = BrailleCanvas::new(3, 2);
+
+        b.draw_circle((2, 2), 2);
+
+        assert!(b.pixel(0, 2));
+        assert!(b.pixel(4, 2));
+        assert!(b.pixel(2, 0));
+        assert!(b.pixel(2, 4));
+        assert!(!b.pixel(2, 2));
+    }
+
+    #[test]
+    fn test_braille_render() {
+        let style = Style::new(Color::Green, Color::None, Modifiers::default());
+        let mut c = Canvas::new_filled(4, 2, Character::new('.', Style::default()));
+        let mut b = BrailleCanvas::new(2, 2);
+
+        b.draw_line((0, 0), (3, 0));
+        b.render(&mut c, (1, 1), style, BlendMode::Overwrite);
+
+        assert_eq!(render(&c), "....\n.⠉⠉.");
+        assert_eq!(c.get(1, 1), Some(&Character::new('⠉', style)));
+    }
+
+    #[test]
+    fn test_braille_far_away() {
+        let mut c = Canvas::new_filled(2, 1, Character::new('.', Style::default()));
+        let mut b = BrailleCanvas::new(2, 2);
+
+        b.draw_circle((usize::MAX, 1), 2);
+        b.draw_circle((1, usize::MAX - 1), 2);
+        assert!(b.cells.iter().all(|c| *c == 0));
+
+        b.draw_line((0, 0), (3, 0));
+        b.render(&mut c, (usize::MAX, 0), Style::default(), BlendMode::Overwrite);
+        b.render(&mut c, (0, usize::MAX), Style::default(), BlendMode::Overwrite);
+        assert_eq!(render(&c), "..");
+    }
+}
@@ -0,0 +1,235 @@
+use super::{canvas::Canvas, style::{Color, Style}};
+
+const BAYER: [[u8; 4]; 4] = [
+    [0, 8, 2, 10],
+    [12, 4, 14, 6],
+    [3, 11, 1, 9],
+    [15, 7, 13, 5]
+];
+
+// How far ordered dithering may push a channel in either direction.
+const BAYER_SPREAD: f32 = 64.0;
+
+const FLOYD_STEINBERG: [(isize, usize, f32); 4] = [
+    (1, 0, 7.0 / 16.0),
+    (-1, 1, 3.0 / 16.0),
+    (0, 1, 5.0 / 16.0),
+    (1, 1, 1.0 / 16.0)
+];
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq
+)]
+pub enum QuantizeTarget {
+    Named,
+    Indexed,
+    Custom(Vec<Color>)
+}
+
+impl QuantizeTarget {
+    pub fn colors(&self) -> Vec<Color> {
+        match self {
+            QuantizeTarget::Named => Color::NAMED.to_vec(),
+            // 0 to 15 repeat the named colours, and terminals theme them anyway.
+            QuantizeTarget::Indexed => (16..=255).map(|i| Color::Indexed { i }).collect(),
+            QuantizeTarget::Custom(colors) => colors.clone()
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum Dither {
+    None,
+    FloydSteinberg,
+    Ordered
+}
+
+// Drops the colours that have no RGB value, like `None` and `Transparent`.
+pub(crate) fn resolve(palette: &[Color]) -> Vec<(Color, [u8; 3])> {
+    palette.iter().filter_map(|c| c.to_rgb().map(|rgb| (*c, rgb))).collect()
+}
+
+pub(crate) fn nearest(colors: &[(Color, [u8; 3])], pixel: [u8; 3]) -> Option<(Color, [u8; 3])> {
+    colors.iter()
+        .min_by_key(|(_, rgb)| {
+            rgb.iter().zip(pixel).map(|(a, b)| (*a as i32 - b as i32).pow(2)).sum::<i32>()
+        })
+        .copied()
+}
+
+fn plane(style: &mut Style, background: bool) -> &mut Color {
+    if background { &mut style.bg } else { &mut style.fg }
+}
+
+impl Canvas {
+    pub fn quantize(&mut self, target: &QuantizeTarget, dither: Dither) {
+        let colors = resolve(&target.colors());
+        if colors.is_empty() {
+            return;
+        }
+
+        self.quantize_plane(&colors, dither, false);
+        self.quantize_plane(&colors, dither, true);
+
+        // Continuations share the style of the wide glyph to their left.
+        for y in 0..self.height() {
+            let row = self.row_mut(y);
+            for x in 1..row.len() {
+                if row[x].is_continuation() {
+                    *row[x].style_mut() = *row[x - 1].style();
+                }
+            }
+        }
+    }
+
+    fn quantize_plane(&mut self, colors: &[(Color, [u8; 3])], dither: Dither, background: bool) {
+        let (width, height) = (self.width(), self.height());
+        let mut error = vec![[0.0f32; 3]; self.cells().len()];
+
+        for (i, cell) in self.cells_mut().iter_mut().enumerate() {
+            if cell.is_continuation() {
+                continue;
+            }
+            let (x, y) = (i % width, i / width);
+            let color = plane(cell.style_mut(), background);
+            let Color::Rgb { r, g, b } = *color else {
+                continue;
+            };
+
+            let offset = match dither {
+                Dither::Ordered => ((BAYER[y % 4][x % 4] as f32 + 0.5) / 16.0 - 0.5) * BAYER_SPREAD,
+                _ => 0.0
+            };
+            let wanted: [f32; 3] = std::array::from_fn(|c| ([r, g, b][c] as f32 + error[i][c] + offset).clamp(0.0, 255.0));
+            let Some((found, rgb)) = nearest(colors, wanted.map(|v| v.round() as u8)) else {
+                continue;
+            };
+            *color = found;
+
+            if dither == Dither::FloydSteinberg {
+                for (dx, dy, weight) in FLOYD_STEINBERG {
+                    let (nx, ny) = (x as isize + dx, y + dy);
+                    if nx < 0 || nx as usize >= width || ny >= height {
+                        continue;
+                    }
+                    let n = &mut error[ny * width + nx as usize];
+                    for c in 0..3 {
+                        n[c] += (wanted[c] - rgb[c] as f32) * weight;
+                    }
+                }
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{character::{BlendMode, Character}, style::Modifiers};
+
+    use super::*;
+
+    fn filled(width: usize, height: usize, fg: Color, bg: Color) -> Canvas {
+        Canvas::new_filled(width, height, Character::new('#', Style::new(fg, bg, Modifiers::default())))
+    }
+
+    fn grey(v: u8) -> Color {
+        Color::Rgb { r: v, g: v, b: v }
+    }
+
+    // 'W' for white and '.' for black foregrounds.
+    fn render(canvas: &Canvas) -> String {
+        canvas.rows().map(|r| {
+            r.iter().map(|c| if c.style().fg == Color::White { 'W' } else { '.' }).collect::<String>()
+        }).collect::<Vec<_>>().join("\n")
+    }
+
+    #[test]
+    fn test_quantize_named() {
+        let mut c = filled(1, 1, Color::Rgb { r: 250, g: 10, b: 10 }, Color::Rgb { r: 0, g: 0, b: 120 });
+        c.quantize(&QuantizeTarget::Named, Dither::None);
+
+        assert_eq!(c.get(0, 0).unwrap().style().fg, Color::Red);
+        assert_eq!(c.get(0, 0).unwrap().style().bg, Color::DarkBlue);
+    }
+
+    #[test]
+    fn test_quantize_indexed() {
+        let mut c = filled(1, 1, Color::Rgb { r: 255, g: 0, b: 0 }, grey(240));
+        c.quantize(&QuantizeTarget::Indexed, Dither::None);
+
+        assert_eq!(c.get(0, 0).unwrap().style().fg, Color::Indexed { i: 196 });
+        assert_eq!(c.get(0, 0).unwrap().style().bg, Color::Indexed { i: 255 });
+    }
+
+    #[test]
+    fn test_quantize_keeps_other_colors() {
+        let mut c = filled(1, 1, Color::Blue, Color::Transparent);
+        c.quantize(&QuantizeTarget::Custom(vec![Color::Black]), Dither::FloydSteinberg);
+
+        assert_eq!(c.get(0, 0).unwrap().style().fg, Color::Blue);
+        assert_eq!(c.get(0, 0).unwrap().style().bg, Color::Transparent);
+    }
+
+    #[test]
+    fn test_quantize_empty_palette() {
+        let mut c = filled(1, 1, grey(10), Color::None);
+        c.quantize(&QuantizeTarget::Custom(vec![Color::None]), Dither::None);
+
+        assert_eq!(c.get(0, 0).unwrap().style().fg, grey(10));
+    }
+
+    #[test]
+    fn test_quantize_floyd_steinberg() {
+        let target = QuantizeTarget::Custom(vec![Color::Black, Color::White]);
+
+        let mut c = filled(8, 1, grey(128), Color::None);
+        c.quantize(&target, Dither::None);
+        assert_eq!(render(&c), "WWWWWWWW");
+
+        let mut c = filled(8, 1, grey(128), Color::None);
+        c.quantize(&target, Dither::FloydSteinberg);
+        assert_eq!(render(&c), "W.W.W.W.");
+    }
+
+    #[test]
+    fn test_quantize_ordered() {
+        let target = QuantizeTarget::Custom(vec![Color::Black, Color::White]);
+        let mut c = filled(4, 2, grey(128), Color::None);
+        c.quantize(&target, Dither::Ordered);
+
+        assert_eq!(render(&c), ".W.W\nW.W.");
+    }
+
+    #[test]
+    fn test_quantize_deterministic() {
+        let gradient = |c: &mut Canvas| c.for_each_cell(|(x, y), c| {
+            *c = Character::new('#', Style::new(Color::Rgb { r: (x * 30) as u8, g: (y * 60) as u8, b: 90 }, Color::None, Modifiers::default()));
+        });
+        let mut a = Canvas::new(8, 4);
+        let mut b = Canvas::new(8, 4);
+        gradient(&mut a);
+        gradient(&mut b);
+
+        a.quantize(&QuantizeTarget::Named, Dither::FloydSteinberg);
+        b.quantize(&QuantizeTarget::Named, Dither::FloydSteinberg);
+        assert_eq!(a.to_buffer(), b.to_buffer());
+    }
+
+    #[test]
+    fn test_quantize_wide() {
+        let mut c = filled(3, 1, grey(128), Color::None);
+        c.draw_character((0, 0), &Character::new('漢', Style::new(grey(128), Color::None, Modifiers::default())), BlendMode::Overwrite);
+        c.quantize(&QuantizeTarget::Custom(vec![Color::Black, Color::White]), Dither::FloydSteinberg);
+
+        assert!(c.get(1, 0).unwrap().is_continuation());
+        assert_eq!(render(&c), "WWW");
+        assert_eq!(c.get(1, 0).unwrap().style(), c.get(0, 0).unwrap().style());
+    }
+}
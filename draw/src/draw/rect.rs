@@ -0,0 +1,94 @@
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq,
+    Default
+)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize
+}
+
+impl Rect {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height
+        }
+    }
+
+    pub fn right(&self) -> usize {
+        self.x.saturating_add(self.width)
+    }
+
+    pub fn bottom(&self) -> usize {
+        self.y.saturating_add(self.height)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn contains(&self, point: (usize, usize)) -> bool {
+        (self.x..self.right()).contains(&point.0) && (self.y..self.bottom()).contains(&point.1)
+    }
+
+    pub fn intersection(&self, other: &Rect) -> Rect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right().min(other.right()).max(x);
+        let bottom = self.bottom().min(other.bottom()).max(y);
+
+        Self::new(x, y, right - x, bottom - y)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect_new() {
+        assert_eq!(
+            Rect::new(1, 2, 3, 4),
+            Rect { x: 1, y: 2, width: 3, height: 4 }
+        );
+    }
+
+    #[test]
+    fn test_rect_edges() {
+        let r = Rect::new(1, 2, 3, 4);
+        assert_eq!(r.right(), 4);
+        assert_eq!(r.bottom(), 6);
+
+        let r = Rect::new(usize::MAX - 1, 2, 3, usize::MAX);
+        assert_eq!(r.right(), usize::MAX);
+        assert_eq!(r.bottom(), usize::MAX);
+    }
+
+    #[test]
+    fn test_rect_contains() {
+        let r = Rect::new(1, 2, 3, 4);
+        assert!(r.contains((1, 2)));
+        assert!(r.contains((3, 5)));
+        assert!(!r.contains((4, 5)));
+        assert!(!r.contains((3, 6)));
+        assert!(!r.contains((0, 3)));
+    }
+
+    #[test]
+    fn test_rect_intersection() {
+        assert_eq!(
+            Rect::new(0, 0, 4, 4).intersection(&Rect::new(2, 1, 5, 2)),
+            Rect::new(2, 1, 2, 2)
+        );
+        assert!(
+            Rect::new(0, 0, 2, 2).intersection(&Rect::new(5, 5, 2, 2)).is_empty()
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use super::{canvas::Canvas, character::{BlendMode, Character}, rect::Rect, style::Style};
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub struct BorderSet {
+    pub top_left: char,
+    pub top: char,
+    pub top_right: char,
+    pub left: char,
+    pub right: char,
+    pub bottom_left: char,
+    pub bottom: char,
+    pub bottom_right: char
+}
+
+impl BorderSet {
+    pub const SINGLE: Self = Self::new('┌', '─', '┐', '│', '│', '└', '─', '┘');
+    pub const DOUBLE: Self = Self::new('╔', '═', '╗', '║', '║', '╚', '═', '╝');
+    pub const ROUNDED: Self = Self::new('╭', '─', '╮', '│', '│', '╰', '─', '╯');
+    pub const HEAVY: Self = Self::new('┏', '━', '┓', '┃', '┃', '┗', '━', '┛');
+    pub const ASCII: Self = Self::new('+', '-', '+', '|', '|', '+', '-', '+');
+
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new(
+        top_left: char, top: char, top_right: char,
+        left: char, right: char,
+        bottom_left: char, bottom: char, bottom_right: char
+    ) -> Self {
+        Self {
+            top_left,
+            top,
+            top_right,
+            left,
+            right,
+            bottom_left,
+            bottom,
+            bottom_right
+        }
+    }
+
+    fn glyph(&self, rect: &Rect, point: (usize, usize)) -> Option<char> {
+        let top = point.1 == rect.y;
+        let bottom = point.1 == rect.bottom() - 1;
+        let left = point.0 == rect.x;
+        let right = point.0 == rect.right() - 1;
+
+        match (top, bottom, left, right) {
+            (true, _, true, _) => Some(self.top_left),
+            (true, _, _, true) => Some(self.top_right),
+            (_, true, true, _) => Some(self.bottom_left),
+            (_, true, _, true) => Some(self.bottom_right),
+            (true, _, _, _) => Some(self.top),
+            (_, true, _, _) => Some(self.bottom),
+            (_, _, true, _) => Some(self.left),
+            (_, _, _, true) => Some(self.right),
+            _ => None
+        }
+    }
+}
+
+impl Default for BorderSet {
+    fn default() -> Self {
+        Self::SINGLE
+    }
+}
+
+
+impl Canvas {
+    pub fn draw_rectangle(&mut self, rect: Rect, border: &BorderSet, style: Style, mode: BlendMode) {
+        let visible = rect.intersection(&self.bounds());
+        if rect.is_empty() || visible.is_empty() {
+            return;
+        }
+
+        let rows = [rect.y, rect.bottom() - 1];
+        let columns = [rect.x, rect.right() - 1];
+        let draw = |canvas: &mut Canvas, point: (usize, usize)| {
+            if let Some(glyph) = border.glyph(&rect, point) {
+                canvas.draw_character(point, &Character::new(glyph, style), mode);
+            }
+        };
+
+        for y in rows.into_iter().filter(|y| (visible.y..visible.bottom()).contains(y)) {
+            for x in visible.x..visible.right() {
+                draw(self, (x, y));
+            }
+            if rows[0] == rows[1] {
+                break;
+            }
+        }
+        for x in columns.into_iter().filter(|x| (visible.x..visible.right()).contains(x)) {
+            for y in (visible.y..visible.bottom()).filter(|y| !rows.contains(y)) {
+                draw(self, (x, y));
+            }
+            if columns[0] == columns[1] {
+                break;
+            }
+        }
+    }
+
+    pub fn fill_rectangle(&mut self, rect: Rect, brush: &Character, mode: BlendMode) {
+        let visible = rect.intersection(&self.bounds());
+        for y in visible.y..visible.bottom() {
+            for x in visible.x..visible.right() {
+                self.draw_character((x, y), brush, mode);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{style::{Color, Modifiers}, test_helpers::render};
+
+    use super::*;
+
+    #[test]
+    fn test_canvas_draw_rectangle_single() {
+        let style = Style::new(Color::Red, Color::Black, Modifiers::new(true, false, false));
+        let mut c = Canvas::new(5, 4);
+
+        c.draw_rectangle(Rect::new(0, 0, 4, 3), &BorderSet::SINGLE, style, BlendMode::Overwrite);
+
+        assert_eq!(c.row(0)[0], Character::new('┌', style));
+        assert_eq!(c.row(1)[1], Character::default());
+        assert_eq!(
+            render(&c),
+            "┌──┐ \n│  │ \n└──┘ \n     "
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_rectangle_border_sets() {
+        let mut c = Canvas::new(12, 3);
+
+        c.draw_rectangle(Rect::new(0, 0, 3, 3), &BorderSet::DOUBLE, Style::default(), BlendMode::Overwrite);
+        c.draw_rectangle(Rect::new(3, 0, 3, 3), &BorderSet::ROUNDED, Style::default(), BlendMode::Overwrite);
+        c.draw_rectangle(Rect::new(6, 0, 3, 3), &BorderSet::HEAVY, Style::default(), BlendMode::Overwrite);
+        c.draw_rectangle(Rect::new(9, 0, 3, 3), &BorderSet::ASCII, Style::default(), BlendMode::Overwrite);
+
+        assert_eq!(
+            render(&c),
+            "╔═╗╭─╮┏━┓+-+\n║ ║│ │┃ ┃| |\n╚═╝╰─╯┗━┛+-+"
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_rectangle_custom() {
+        let mut c = Canvas::new(3, 3);
+
+        c.draw_rectangle(
+            Rect::new(0, 0, 3, 3),
+            &BorderSet::new('1', '2', '3', '4', '5', '6', '7', '8'),
+            Style::default(),
+            BlendMode::Overwrite
+        );
+
+        assert_eq!(
+            render(&c),
+            "123\n4 5\n678"
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_rectangle_degenerate() {
+        let mut c = Canvas::new(4, 4);
+
+        c.draw_rectangle(Rect::new(0, 0, 4, 1), &BorderSet::SINGLE, Style::default(), BlendMode::Overwrite);
+        c.draw_rectangle(Rect::new(0, 1, 1, 3), &BorderSet::SINGLE, Style::default(), BlendMode::Overwrite);
+        c.draw_rectangle(Rect::new(2, 2, 0, 2), &BorderSet::SINGLE, Style::default(), BlendMode::Overwrite);
+
+        assert_eq!(
+            render(&c),
+            "┌──┐\n┌   \n│   \n└   "
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_rectangle_clips() {
+        let mut c = Canvas::new(3, 2);
+
+        c.draw_rectangle(Rect::new(1, 0, 5, 5), &BorderSet::SINGLE, Style::default(), BlendMode::Overwrite);
+
+        assert_eq!(
+            render(&c),
+            " ┌─\n │ "
+        );
+    }
+
+    #[test]
+    fn test_canvas_rectangle_huge() {
+        let mut c = Canvas::new(3, 2);
+
+        c.draw_rectangle(Rect::new(1, 1, usize::MAX, usize::MAX), &BorderSet::SINGLE, Style::default(), BlendMode::Overwrite);
+        c.fill_rectangle(Rect::new(usize::MAX, 0, usize::MAX, 1), &Character::new('#', Style::default()), BlendMode::Overwrite);
+        c.fill_rectangle(Rect::new(0, 0, usize::MAX, 1), &Character::new('#', Style::default()), BlendMode::Overwrite);
+
+        assert_eq!(
+            render(&c),
+            "###\n ┌─"
+        );
+    }
+
+    #[test]
+    fn test_canvas_fill_rectangle_wide() {
+        let mut c = Canvas::new(6, 2);
+
+        c.fill_rectangle(c.bounds(), &Character::new('漢', Style::default()), BlendMode::Overwrite);
+
+        assert_eq!(
+            render(&c),
+            "漢漢漢\n漢漢漢"
+        );
+    }
+
+    #[test]
+    fn test_canvas_fill_rectangle() {
+        let fill = Character::new('_', Style::new(Color::Red, Color::Black, Modifiers::new(false, false, false)));
+        let brush = Character::new('#', Style::new(Color::DarkCyan, Color::Red, Modifiers::new(true, true, false)));
+        let mut c = Canvas::new_filled(4, 3, fill.clone());
+
+        c.fill_rectangle(Rect::new(1, 1, 5, 1), &brush, BlendMode::OnlyStyle);
+
+        assert_eq!(
+            c.to_buffer(),
+            vec![
+                vec![fill.clone(), fill.clone(), fill.clone(), fill.clone()],
+                vec![fill.clone(), Character::new('_', *brush.style()), Character::new('_', *brush.style()), Character::new('_', *brush.style())],
+                vec![fill.clone(), fill.clone(), fill.clone(), fill]
+            ]
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use super::{canvas::{self, Canvas}, character::{BlendMode, Character}, rect::Rect};
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum Transparency {
+    Opaque,
+    DefaultTransparent
+}
+
+impl Transparency {
+    fn is_transparent(&self, character: &Character) -> bool {
+        match self {
+            Transparency::Opaque => false,
+            Transparency::DefaultTransparent => *character == Character::default()
+        }
+    }
+}
+
+
+impl Canvas {
+    pub fn crop(&self, rect: Rect) -> Canvas {
+        let mut crop = self.crop_cells(rect);
+        for y in 0..crop.height() {
+            canvas::repair_wide(crop.row_mut(y));
+        }
+        crop
+    }
+
+    // Like `crop`, but keeps the halves of wide glyphs that the edges cut through, so they can be written back.
+    pub(crate) fn crop_cells(&self, rect: Rect) -> Canvas {
+        Canvas::new(rect.width, rect.height).map_cells(|(x, y), c| {
+            match (rect.x.checked_add(x), rect.y.checked_add(y)) {
+                (Some(x), Some(y)) => self.get(x, y).unwrap_or(c).clone(),
+                _ => c.clone()
+            }
+        })
+    }
+
+    pub fn paste(&mut self, source: &Canvas, at: (usize, usize), mode: BlendMode, transparency: Transparency) {
+        for ((x, y), c) in source.enumerate_cells() {
+            let (Some(x), Some(y)) = (at.0.checked_add(x), at.1.checked_add(y)) else {
+                continue;
+            };
+            if !transparency.is_transparent(c) && self.bounds().contains((x, y)) {
+                self.draw_character((x, y), c, mode);
+            }
+        }
+    }
+
+    pub fn copy_region(&mut self, region: Rect, to: (usize, usize), mode: BlendMode, transparency: Transparency) {
+        let source = self.crop(region);
+        self.paste(&source, to, mode, transparency);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{style::{Style, Color, Modifiers}, test_helpers::{render, canvas}};
+
+    use super::*;
+
+    #[test]
+    fn test_canvas_crop() {
+        let c = canvas("abcd\nefgh\nijkl");
+
+        assert_eq!(
+            render(&c.crop(Rect::new(1, 1, 2, 2))),
+            "fg\njk"
+        );
+    }
+
+    #[test]
+    fn test_canvas_crop_splits_wide() {
+        let c = canvas("漢~字");
+
+        let cropped = c.crop(Rect::new(1, 0, 3, 1));
+        assert_eq!(render(&cropped), " ~ ");
+        assert!(!cropped.row(0).iter().any(|c| c.is_continuation() || c.is_wide()));
+
+        assert_eq!(render(&c.crop(Rect::new(0, 0, 4, 1))), "漢~ ");
+        assert!(c.crop(Rect::new(0, 0, 4, 1)).row(0)[1].is_continuation());
+    }
+
+    #[test]
+    fn test_canvas_crop_outside_is_default() {
+        let c = canvas("ab\ncd");
+        let cropped = c.crop(Rect::new(1, 1, 2, 2));
+
+        assert_eq!(
+            cropped.to_buffer(),
+            vec![
+                vec![Character::new('d', Style::default()), Character::default()],
+                vec![Character::default(), Character::default()]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_canvas_crop_far_away() {
+        let c = canvas("ab\ncd");
+
+        assert_eq!(render(&c.crop(Rect::new(usize::MAX, 0, 2, 1))), "  ");
+        assert_eq!(render(&c.crop(Rect::new(1, usize::MAX, 1, 2))), " \n ");
+    }
+
+    #[test]
+    fn test_canvas_crop_empty() {
+        let c = canvas("ab\ncd");
+        let cropped = c.crop(Rect::new(0, 0, 2, 0));
+
+        assert_eq!((cropped.width(), cropped.height()), (2, 0));
+    }
+
+    #[test]
+    fn test_canvas_paste_clips() {
+        let mut c = canvas("....\n....\n....");
+        let source = canvas("ab\ncd");
+
+        c.paste(&source, (3, 2), BlendMode::Overwrite, Transparency::Opaque);
+
+        assert_eq!(
+            render(&c),
+            "....\n....\n...a"
+        );
+    }
+
+    #[test]
+    fn test_canvas_paste_far_away() {
+        let mut c = canvas("..\n..");
+        let source = canvas("ab\ncd");
+
+        c.paste(&source, (usize::MAX, 0), BlendMode::Overwrite, Transparency::Opaque);
+        c.paste(&source, (1, usize::MAX), BlendMode::Overwrite, Transparency::Opaque);
+
+        assert_eq!(render(&c), "..\n..");
+    }
+
+    #[test]
+    fn test_canvas_paste_transparency() {
+        let mut c = canvas("....\n....");
+        let source = canvas("a \n d");
+
+        c.paste(&source, (1, 0), BlendMode::Overwrite, Transparency::Opaque);
+        assert_eq!(
+            render(&c),
+            ".a .\n. d."
+        );
+
+        let mut c = canvas("....\n....");
+        c.paste(&source, (1, 0), BlendMode::Overwrite, Transparency::DefaultTransparent);
+        assert_eq!(
+            render(&c),
+            ".a..\n..d."
+        );
+    }
+
+    #[test]
+    fn test_canvas_paste_styled_space_is_opaque() {
+        let space = Character::new(' ', Style::new(Color::None, Color::Red, Modifiers::default()));
+        let mut c = canvas("..");
+        let source = Canvas::new_with_buffer(vec![vec![space.clone(), Character::default()]]);
+
+        c.paste(&source, (0, 0), BlendMode::Overwrite, Transparency::DefaultTransparent);
+
+        assert_eq!(
+            c.to_buffer(),
+            vec![vec![space, Character::new('.', Style::default())]]
+        );
+    }
+
+    #[test]
+    fn test_canvas_paste_transparent_cells() {
+        let mut c = canvas("...");
+        let source = Canvas::new_with_buffer(vec![vec![
+            Character::transparent(),
+            Character::new('b', Style::default()),
+            Character::new_transparent(Style::new(Color::Red, Color::Transparent, Modifiers::default()))
+        ]]);
+
+        c.paste(&source, (0, 0), BlendMode::Overwrite, Transparency::Opaque);
+
+        assert_eq!(
+            c.to_buffer(),
+            vec![vec![
+                Character::new('.', Style::default()),
+                Character::new('b', Style::default()),
+                Character::new('.', Style::new(Color::Red, Color::None, Modifiers::default()))
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_canvas_copy_region_overlapping() {
+        let mut c = canvas("abc.\n....");
+
+        c.copy_region(Rect::new(0, 0, 3, 1), (1, 0), BlendMode::Overwrite, Transparency::Opaque);
+
+        assert_eq!(
+            render(&c),
+            "aabc\n...."
+        );
+    }
+}
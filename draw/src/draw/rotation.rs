@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+
+use super::{box_drawing, canvas::{self, Canvas}};
+
+// Each cycle lists a glyph followed by its clockwise rotations.
+const CYCLES: [&[char]; 24] = [
+    &['─', '│'], &['━', '┃'], &['═', '║'],
+    &['┄', '┆'], &['┅', '┇'], &['┈', '┊'], &['┉', '┋'], &['╌', '╎'], &['╍', '╏'],
+    &['╭', '╮', '╯', '╰'], &['╱', '╲'], &['/', '\\'], &['-', '|'],
+    &['→', '↓', '←', '↑'], &['▶', '▼', '◀', '▲'], &['▷', '▽', '◁', '△'],
+    &['▀', '▐', '▄', '▌'], &['▔', '▕', '▁', '▏'], &['▘', '▝', '▗', '▖'], &['▛', '▜', '▟', '▙'], &['▚', '▞'],
+    &['◤', '◥', '◢', '◣'], &['◐', '◓', '◑', '◒'], &['⌐', '¬']
+];
+
+// Arrows drawn with letters, left out of the defaults so rotating text doesn't change its words.
+const LETTER_CYCLES: [&[char]; 1] = [&['<', '^', '>', 'v']];
+
+const SYMMETRIC: [char; 22] = [
+    ' ', '█', '░', '▒', '▓', '■', '□', '▪', '▫', '●', '○', '◆', '◇', '◉',
+    '+', '*', '#', 'o', 'O', 'x', 'X', '.'
+];
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum Rotation {
+    Clockwise90,
+    Clockwise180,
+    Clockwise270
+}
+
+impl Rotation {
+    fn turns(&self) -> usize {
+        match self {
+            Rotation::Clockwise90 => 1,
+            Rotation::Clockwise180 => 2,
+            Rotation::Clockwise270 => 3
+        }
+    }
+}
+
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum RotationFallback {
+    Keep,
+    Replace(char)
+}
+
+
+#[derive(Debug, Clone)]
+pub struct RotationTable {
+    clockwise: HashMap<char, char>,
+    pub fallback: RotationFallback
+}
+
+impl RotationTable {
+    pub fn new(fallback: RotationFallback) -> Self {
+        Self {
+            clockwise: HashMap::new(),
+            fallback
+        }
+    }
+
+    pub fn insert(&mut self, from: char, to: char) {
+        self.clockwise.insert(from, to);
+    }
+
+    pub fn insert_cycle(&mut self, cycle: &[char]) {
+        for (i, c) in cycle.iter().enumerate() {
+            self.insert(*c, cycle[(i + 1) % cycle.len()]);
+        }
+    }
+
+    pub fn insert_letters(&mut self) {
+        for cycle in LETTER_CYCLES {
+            self.insert_cycle(cycle);
+        }
+    }
+
+    fn rotate_clockwise(&self, c: char) -> Option<char> {
+        if let Some(r) = self.clockwise.get(&c) {
+            return Some(*r);
+        }
+
+        let [up, right, down, left] = box_drawing::arms(c)?;
+        let rotated = [left, up, right, down];
+        if rotated == [up, right, down, left] {
+            Some(c)
+        } else {
+            box_drawing::glyph(rotated)
+        }
+    }
+
+    pub fn rotate(&self, c: char, rotation: Rotation) -> char {
+        let mut rotated = c;
+        for _ in 0..rotation.turns() {
+            rotated = match self.rotate_clockwise(rotated) {
+                Some(r) => r,
+                None => return match self.fallback {
+                    RotationFallback::Keep => c,
+                    RotationFallback::Replace(r) => r
+                }
+            };
+        }
+        rotated
+    }
+}
+
+impl Default for RotationTable {
+    fn default() -> Self {
+        let mut table = Self::new(RotationFallback::Keep);
+        for cycle in CYCLES {
+            table.insert_cycle(cycle);
+        }
+        for c in SYMMETRIC {
+            table.insert(c, c);
+        }
+        table
+    }
+}
+
+
+impl Canvas {
+    pub fn rotate(&self, rotation: Rotation, table: &RotationTable) -> Canvas {
+        let (width, height) = (self.width(), self.height());
+        let cell = |x: usize, y: usize| match rotation {
+            Rotation::Clockwise90 => &self.row(height - 1 - x)[y],
+            Rotation::Clockwise180 => &self.row(height - 1 - y)[width - 1 - x],
+            Rotation::Clockwise270 => &self.row(x)[width - 1 - y]
+        };
+        let (width, height) = match rotation {
+            Rotation::Clockwise180 => (width, height),
+            _ => (height, width)
+        };
+
+        let mut rotated = Canvas::new(width, height).map_cells(|(x, y), _| {
+            let mut c = cell(x, y).clone();
+            // Wide glyphs can't stand upright in a single column.
+            if rotation != Rotation::Clockwise180 && (c.is_wide() || c.is_continuation()) {
+                c.set_continuation(false);
+                c.set_character(' ');
+            }
+            c.set_character(table.rotate(c.character(), rotation));
+            c
+        });
+        if rotation == Rotation::Clockwise180 {
+            for y in 0..height {
+                canvas::swap_reversed_wide(rotated.row_mut(y));
+            }
+        }
+        rotated
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{character::Character, style::{Style, Color, Modifiers}, test_helpers::{render, canvas}};
+
+    use super::*;
+
+    #[test]
+    fn test_rotation_table_default() {
+        let table = RotationTable::default();
+
+        assert_eq!(table.rotate('─', Rotation::Clockwise90), '│');
+        assert_eq!(table.rotate('─', Rotation::Clockwise180), '─');
+        assert_eq!(table.rotate('→', Rotation::Clockwise90), '↓');
+        assert_eq!(table.rotate('→', Rotation::Clockwise270), '↑');
+        assert_eq!(table.rotate('▀', Rotation::Clockwise90), '▐');
+        assert_eq!(table.rotate('▀', Rotation::Clockwise180), '▄');
+        assert_eq!(table.rotate('┌', Rotation::Clockwise90), '┐');
+        assert_eq!(table.rotate('├', Rotation::Clockwise90), '┬');
+        assert_eq!(table.rotate('╒', Rotation::Clockwise90), '╖');
+        assert_eq!(table.rotate('╭', Rotation::Clockwise270), '╰');
+        assert_eq!(table.rotate('┼', Rotation::Clockwise90), '┼');
+        assert_eq!(table.rotate(' ', Rotation::Clockwise90), ' ');
+        assert_eq!(table.rotate('a', Rotation::Clockwise90), 'a');
+        assert_eq!(table.rotate('v', Rotation::Clockwise90), 'v');
+        assert_eq!(table.rotate('<', Rotation::Clockwise90), '<');
+    }
+
+    #[test]
+    fn test_rotation_table_letters() {
+        let mut table = RotationTable::default();
+        table.insert_letters();
+
+        assert_eq!(table.rotate('<', Rotation::Clockwise90), '^');
+        assert_eq!(table.rotate('v', Rotation::Clockwise90), '<');
+        assert_eq!(table.rotate('>', Rotation::Clockwise180), '<');
+        assert_eq!(table.rotate('→', Rotation::Clockwise90), '↓');
+    }
+
+    #[test]
+    fn test_rotation_table_fallback() {
+        let mut table = RotationTable {
+            fallback: RotationFallback::Replace('?'),
+            ..Default::default()
+        };
+
+        assert_eq!(table.rotate('a', Rotation::Clockwise90), '?');
+        assert_eq!(table.rotate('█', Rotation::Clockwise90), '█');
+
+        table.insert('a', 'b');
+        assert_eq!(table.rotate('a', Rotation::Clockwise90), 'b');
+        assert_eq!(table.rotate('a', Rotation::Clockwise180), '?');
+    }
+
+    #[test]
+    fn test_canvas_rotate_90() {
+        let c = canvas("┌─→\n│ ▀");
+
+        let r = c.rotate(Rotation::Clockwise90, &RotationTable::default());
+
+        assert_eq!(r.width(), 2);
+        assert_eq!(r.height(), 3);
+        assert_eq!(
+            render(&r),
+            "─┐\n │\n▐↓"
+        );
+    }
+
+    #[test]
+    fn test_canvas_rotate_180() {
+        let c = canvas("┌─→\n│ ▀");
+
+        assert_eq!(
+            render(&c.rotate(Rotation::Clockwise180, &RotationTable::default())),
+            "▄ │\n←─┘"
+        );
+    }
+
+    #[test]
+    fn test_canvas_rotate_270() {
+        let c = canvas("┌─→\n│ ▀");
+
+        assert_eq!(
+            render(&c.rotate(Rotation::Clockwise270, &RotationTable::default())),
+            "↑▌\n│ \n└─"
+        );
+    }
+
+    #[test]
+    fn test_canvas_rotate_empty() {
+        let table = RotationTable::default();
+        let c = Canvas::new(0, 3);
+
+        let size = |c: &Canvas| (c.width(), c.height());
+        assert_eq!(size(&c.rotate(Rotation::Clockwise90, &table)), (3, 0));
+        assert_eq!(size(&c.rotate(Rotation::Clockwise180, &table)), (0, 3));
+        assert_eq!(size(&c.rotate(Rotation::Clockwise270, &table)), (3, 0));
+        assert_eq!(size(&Canvas::new(2, 0).rotate(Rotation::Clockwise90, &table)), (0, 2));
+    }
+
+    #[test]
+    fn test_canvas_rotate_wide() {
+        let c = canvas("漢~");
+        let table = RotationTable::default();
+
+        let rotated = c.rotate(Rotation::Clockwise180, &table);
+        assert_eq!(render(&rotated), "~漢");
+        assert!(rotated.row(0)[2].is_continuation());
+
+        for rotation in [Rotation::Clockwise90, Rotation::Clockwise270] {
+            let rotated = c.rotate(rotation, &table);
+            assert!(!rotated.cells().iter().any(|c| c.is_wide() || c.is_continuation()));
+        }
+        assert_eq!(render(&c.rotate(Rotation::Clockwise90, &table)), " \n \n~");
+    }
+
+    #[test]
+    fn test_canvas_rotate_keeps_style() {
+        let style = Style::new(Color::Red, Color::Black, Modifiers::new(true, false, false));
+        let c = Canvas::new_with_buffer(vec![vec![Character::new('─', style), Character::default()]]);
+
+        assert_eq!(
+            c.rotate(Rotation::Clockwise90, &RotationTable::default()).to_buffer(),
+            vec![
+                vec![Character::new('│', style)],
+                vec![Character::default()]
+            ]
+        );
+    }
+}
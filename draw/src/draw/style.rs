@@ -14,7 +14,20 @@ pub enum Color {
     None,
     Black,    Red,     Green,     Yellow,     Blue,     Magenta,     Cyan,     LightGray,
     DarkGray, DarkRed, DarkGreen, DarkYellow, DarkBlue, DarkMagenta, DarkCyan, White,
-    Indexed { i: u8 }, Rgb { r: u8, g: u8, b: u8 }
+    Indexed { i: u8 }, Rgb { r: u8, g: u8, b: u8 },
+    Transparent
+}
+
+impl Color {
+    pub fn is_transparent(&self) -> bool {
+        *self == Color::Transparent
+    }
+
+    fn blend(&mut self, rhs: &Self) {
+        if !rhs.is_transparent() {
+            *self = *rhs;
+        }
+    }
 }
 
 
@@ -89,6 +102,20 @@ impl Style {
             modifiers
         }
     }
+
+    pub fn transparent() -> Self {
+        Self::new(
+            Color::Transparent,
+            Color::Transparent,
+            Modifiers::default()
+        )
+    }
+
+    pub fn blend(&mut self, rhs: &Self) {
+        self.fg.blend(&rhs.fg);
+        self.bg.blend(&rhs.bg);
+        self.modifiers.copy_from(&rhs.modifiers);
+    }
 }
 
 impl Default for Style {
@@ -105,11 +132,11 @@ impl Add for Style {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self::new(
-            rhs.fg,
-            rhs.bg,
-            self.modifiers + rhs.modifiers
-        )
+        let mut ret = self;
+        ret.fg.blend(&rhs.fg);
+        ret.bg.blend(&rhs.bg);
+        ret.modifiers = self.modifiers + rhs.modifiers;
+        ret
     }
 }
 
@@ -204,6 +231,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_style_add_transparent() {
+        assert_eq!(
+            Style::new(Color::Red, Color::Blue, Modifiers::new(true, false, false))
+                + Style::new(Color::Transparent, Color::Yellow, Modifiers::new(false, true, false)),
+            Style { fg: Color::Red, bg: Color::Yellow, modifiers: Modifiers::new(true, true, false) }
+        );
+    }
+
+    #[test]
+    fn test_style_transparent() {
+        assert_eq!(
+            Style::transparent(),
+            Style { fg: Color::Transparent, bg: Color::Transparent, modifiers: Modifiers::default() }
+        );
+    }
+
+    #[test]
+    fn test_style_blend() {
+        let mut s1 = Style::new(Color::Cyan, Color::Yellow, Modifiers::new(true, false, true));
+
+        s1.blend(&Style::new(Color::Transparent, Color::Green, Modifiers::new(false, true, false)));
+        assert_eq!(s1, Style::new(Color::Cyan, Color::Green, Modifiers::new(false, true, false)));
+
+        s1.blend(&Style::new(Color::Blue, Color::Transparent, Modifiers::default()));
+        assert_eq!(s1, Style::new(Color::Blue, Color::Green, Modifiers::default()));
+    }
+
     #[test]
     fn test_style_copy_from() {
         let mut s1 = Style::new(Color::Cyan, Color::Yellow, Modifiers::new(true, false, true));
@@ -0,0 +1,11 @@
+use crate::loaders::{txt::LoaderTxt, loader::{Exporter, Importer}};
+
+use super::canvas::Canvas;
+
+pub(crate) fn render(canvas: &Canvas) -> String {
+    LoaderTxt::export(&canvas.to_buffer())
+}
+
+pub(crate) fn canvas(text: &str) -> Canvas {
+    Canvas::new_with_buffer(LoaderTxt::import(text))
+}
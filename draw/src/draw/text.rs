@@ -0,0 +1,368 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{canvas::Canvas, character::{BlendMode, Character}, grapheme::Grapheme, rect::Rect, style::Style};
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum TextWrap {
+    Word,
+    Hard,
+    Truncate
+}
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum Alignment {
+    Left,
+    Center,
+    Right,
+    Justify
+}
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub struct TextOptions {
+    pub wrap: TextWrap,
+    pub alignment: Alignment,
+    pub ellipsis: Option<char>
+}
+
+impl TextOptions {
+    pub fn new(wrap: TextWrap, alignment: Alignment, ellipsis: Option<char>) -> Self {
+        Self {
+            wrap,
+            alignment,
+            ellipsis
+        }
+    }
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self::new(
+            TextWrap::Word,
+            Alignment::Left,
+            Some('…')
+        )
+    }
+}
+
+
+struct Line {
+    text: Vec<Grapheme>,
+    last: bool
+}
+
+fn glyphs(text: &str) -> Vec<Grapheme> {
+    text.graphemes(true).map(Grapheme::new).collect()
+}
+
+fn width(text: &[Grapheme]) -> usize {
+    text.iter().map(Grapheme::width).sum()
+}
+
+// Number of glyphs from the start of `text` that fit in `width` columns.
+fn fitting(text: &[Grapheme], width: usize) -> usize {
+    let mut used = 0;
+    text.iter().take_while(|g| {
+        used += g.width();
+        used <= width
+    }).count()
+}
+
+// Takes at least one glyph, so wrapping moves on even when a wide glyph doesn't fit.
+fn split_line(text: &mut Vec<Grapheme>, width: usize) -> Vec<Grapheme> {
+    let rest = text.split_off(fitting(text, width).clamp(1, text.len()));
+    std::mem::replace(text, rest)
+}
+
+fn truncate(text: &mut Vec<Grapheme>, width: usize, ellipsis: Option<char>) {
+    match ellipsis.map(Grapheme::from) {
+        Some(e) if width >= e.width() => {
+            text.truncate(fitting(text, width - e.width()));
+            text.push(e);
+        },
+        _ => text.truncate(fitting(text, width))
+    }
+}
+
+fn wrap_words(paragraph: &str, max: usize) -> Vec<Vec<Grapheme>> {
+    let mut lines = Vec::new();
+    let mut line: Vec<Grapheme> = Vec::new();
+    for word in paragraph.split_whitespace() {
+        let mut word = glyphs(word);
+        if !line.is_empty() && width(&line) + 1 + width(&word) <= max {
+            line.push(Grapheme::from(' '));
+            line.append(&mut word);
+            continue;
+        }
+
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        while width(&word) > max {
+            lines.push(split_line(&mut word, max));
+        }
+        line = word;
+    }
+    lines.push(line);
+    lines
+}
+
+fn layout(text: &str, max: usize, options: &TextOptions) -> Vec<Line> {
+    text.lines().flat_map(|paragraph| {
+        let lines = match options.wrap {
+            TextWrap::Word => wrap_words(paragraph, max),
+            TextWrap::Hard => {
+                let mut rest = glyphs(paragraph);
+                let mut lines = vec![split_line(&mut rest, max)];
+                while !rest.is_empty() {
+                    lines.push(split_line(&mut rest, max));
+                }
+                lines
+            },
+            TextWrap::Truncate => {
+                let mut line = glyphs(paragraph);
+                if width(&line) > max {
+                    truncate(&mut line, max, options.ellipsis);
+                }
+                vec![line]
+            }
+        };
+
+        let count = lines.len();
+        lines.into_iter().enumerate().map(move |(i, text)| Line { text, last: i + 1 == count })
+    }).collect()
+}
+
+fn justify(text: &[Grapheme], max: usize) -> Vec<Grapheme> {
+    let words: Vec<&[Grapheme]> = text.split(|g| g.as_str() == " ").filter(|w| !w.is_empty()).collect();
+    if words.len() < 2 {
+        return text.to_vec();
+    }
+
+    let gaps = words.len() - 1;
+    let spaces = max.saturating_sub(words.iter().map(|w| width(w)).sum::<usize>());
+    let mut line = Vec::with_capacity(text.len() + spaces);
+    for (i, word) in words.iter().enumerate() {
+        line.extend_from_slice(word);
+        if i < gaps {
+            let count = spaces / gaps + usize::from(i < spaces % gaps);
+            line.extend(std::iter::repeat_n(Grapheme::from(' '), count));
+        }
+    }
+    line
+}
+
+impl Canvas {
+    pub fn draw_text(&mut self, rect: Rect, text: &str, style: Style, options: TextOptions) {
+        if rect.is_empty() {
+            return;
+        }
+
+        let mut lines = layout(text, rect.width, &options);
+        if lines.len() > rect.height {
+            lines.truncate(rect.height);
+            if let Some(last) = lines.last_mut() {
+                last.last = true;
+                if let Some(e) = options.ellipsis {
+                    let width = (width(&last.text) + Grapheme::from(e).width()).min(rect.width);
+                    truncate(&mut last.text, width, options.ellipsis);
+                }
+            }
+        }
+
+        for (y, line) in lines.iter().enumerate() {
+            let text = match options.alignment {
+                Alignment::Justify if !line.last => justify(&line.text, rect.width),
+                _ => line.text.clone()
+            };
+            let x = match options.alignment {
+                Alignment::Left | Alignment::Justify => 0,
+                Alignment::Center => rect.width.saturating_sub(width(&text)) / 2,
+                Alignment::Right => rect.width.saturating_sub(width(&text))
+            };
+
+            let Some(y) = rect.y.checked_add(y) else {
+                break;
+            };
+            let Some(mut x) = rect.x.checked_add(x) else {
+                continue;
+            };
+            for g in text {
+                let c = Character::new(g, style);
+                let Some(end) = x.checked_add(c.width()).filter(|end| *end <= rect.right()) else {
+                    break;
+                };
+                self.draw_character((x, y), &c, BlendMode::Overwrite);
+                x = end;
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::draw::{style::{Color, Modifiers}, test_helpers::render};
+
+    use super::*;
+
+    fn draw(width: usize, height: usize, text: &str, options: TextOptions) -> String {
+        let mut c = Canvas::new_filled(width, height, Character::new('.', Style::default()));
+        c.draw_text(Rect::new(0, 0, width, height), text, Style::default(), options);
+        render(&c)
+    }
+
+    #[test]
+    fn test_canvas_draw_text_style() {
+        let style = Style::new(Color::Red, Color::Black, Modifiers::new(true, false, false));
+        let mut c = Canvas::new(4, 2);
+
+        c.draw_text(Rect::new(1, 1, 3, 1), "hi", style, TextOptions::default());
+
+        assert_eq!(c.row(1)[1], Character::new('h', style));
+        assert_eq!(c.row(1)[3], Character::default());
+        assert_eq!(render(&c), "    \n hi ");
+    }
+
+    #[test]
+    fn test_canvas_draw_text_wide() {
+        assert_eq!(
+            draw(5, 1, "a漢字x", TextOptions::new(TextWrap::Truncate, Alignment::Left, None)),
+            "a漢字"
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_text_wide_layout() {
+        assert_eq!(
+            draw(6, 1, "漢字", TextOptions::new(TextWrap::Word, Alignment::Right, None)),
+            "..漢字"
+        );
+        assert_eq!(
+            draw(6, 1, "漢字", TextOptions::new(TextWrap::Word, Alignment::Center, None)),
+            ".漢字."
+        );
+        assert_eq!(
+            draw(4, 2, "漢字漢字", TextOptions::default()),
+            "漢字\n漢字"
+        );
+        assert_eq!(
+            draw(5, 2, "漢字漢", TextOptions::new(TextWrap::Hard, Alignment::Left, None)),
+            "漢字.\n漢..."
+        );
+        assert_eq!(
+            draw(5, 1, "漢字漢字", TextOptions::new(TextWrap::Truncate, Alignment::Left, Some('…'))),
+            "漢字…"
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_text_graphemes() {
+        let mut c = Canvas::new(4, 1);
+        c.draw_text(Rect::new(0, 0, 4, 1), "e\u{301}x👩\u{200D}💻", Style::default(), TextOptions::default());
+
+        assert_eq!(c.row(0)[0].grapheme(), "e\u{301}");
+        assert_eq!(c.row(0)[1].grapheme(), "x");
+        assert_eq!(c.row(0)[2].grapheme(), "👩\u{200D}💻");
+        assert!(c.row(0)[3].is_continuation());
+
+        assert_eq!(
+            draw(3, 1, "e\u{301}e\u{301}e\u{301}e\u{301}", TextOptions::new(TextWrap::Truncate, Alignment::Left, Some('…'))),
+            "e\u{301}e\u{301}…"
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_text_word_wrap() {
+        assert_eq!(
+            draw(10, 4, "the quick brown fox jumps", TextOptions::default()),
+            "the quick.\nbrown fox.\njumps.....\n.........."
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_text_word_wrap_long_word() {
+        assert_eq!(
+            draw(4, 4, "a abcdefghij", TextOptions::default()),
+            "a...\nabcd\nefgh\nij.."
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_text_hard_wrap() {
+        assert_eq!(
+            draw(4, 3, "hello world\nab", TextOptions::new(TextWrap::Hard, Alignment::Left, None)),
+            "hell\no wo\nrld."
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_text_truncate() {
+        assert_eq!(
+            draw(6, 2, "hello world\nshort", TextOptions::new(TextWrap::Truncate, Alignment::Left, Some('…'))),
+            "hello…\nshort."
+        );
+        assert_eq!(
+            draw(6, 1, "hello world", TextOptions::new(TextWrap::Truncate, Alignment::Left, None)),
+            "hello "
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_text_vertical_overflow() {
+        assert_eq!(
+            draw(5, 2, "aaa bbb ccc", TextOptions::default()),
+            "aaa..\nbbb…."
+        );
+        assert_eq!(
+            draw(3, 1, "aaa bbb", TextOptions::default()),
+            "aa…"
+        );
+        assert_eq!(
+            draw(5, 1, "aaa bbb", TextOptions::new(TextWrap::Word, Alignment::Left, None)),
+            "aaa.."
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_text_far_away() {
+        let mut c = Canvas::new_filled(3, 2, Character::new('.', Style::default()));
+
+        c.draw_text(Rect::new(usize::MAX - 1, 0, 5, 2), "abc", Style::default(), TextOptions::default());
+        c.draw_text(Rect::new(1, usize::MAX, 2, 3), "ab cd ef", Style::default(), TextOptions::default());
+        c.draw_text(Rect::new(0, 1, usize::MAX, 1), "ab", Style::default(), TextOptions::new(TextWrap::Word, Alignment::Right, None));
+
+        assert_eq!(render(&c), "...\n...");
+    }
+
+    #[test]
+    fn test_canvas_draw_text_alignment() {
+        assert_eq!(
+            draw(7, 2, "ab\ncdef", TextOptions::new(TextWrap::Word, Alignment::Right, None)),
+            ".....ab\n...cdef"
+        );
+        assert_eq!(
+            draw(7, 2, "ab\ncdef", TextOptions::new(TextWrap::Word, Alignment::Center, None)),
+            "..ab...\n.cdef.."
+        );
+    }
+
+    #[test]
+    fn test_canvas_draw_text_justify() {
+        assert_eq!(
+            draw(10, 3, "a bb ccc dd e\nf g", TextOptions::new(TextWrap::Word, Alignment::Justify, None)),
+            "a  bb  ccc\ndd e......\nf g......."
+        );
+    }
+}
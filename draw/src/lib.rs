@@ -2,6 +2,26 @@ mod draw;
 mod traits;
 mod loaders;
 
-pub use crate::draw::canvas::Canvas;
+pub use crate::draw::canvas::{Canvas, CanvasError};
+pub use crate::draw::box_drawing;
 pub use crate::draw::character;
+pub use crate::draw::color;
+pub use crate::draw::diff::{self, diff};
+pub use crate::draw::document;
+pub use crate::draw::ellipse;
+pub use crate::draw::figlet;
+pub use crate::draw::fill;
+pub use crate::draw::grapheme;
+pub use crate::draw::history;
+pub use crate::draw::line;
+pub use crate::draw::mirror;
+pub use crate::draw::palette;
+pub use crate::draw::pixel;
+pub use crate::draw::quantize;
+pub use crate::draw::rect;
+pub use crate::draw::rectangle;
+pub use crate::draw::region;
+pub use crate::draw::rotation;
 pub use crate::draw::style;
+pub use crate::draw::text;
+pub use crate::loaders::{bin, image, loader, txt};
@@ -1,3 +1,4 @@
 pub mod loader;
 pub mod txt;
 pub mod bin;
+pub mod image;
@@ -1,23 +1,83 @@
-use bincode;
+use bincode::{self, Options};
+use serde::{de::DeserializeOwned, Deserialize};
 
 use super::loader::*;
-use crate::draw::character::Character;
+use crate::draw::{character::Character, style::Style};
+
+// Written in front of the cells, files without it hold one of the untagged layouts below.
+const MAGIC: &[u8; 4] = b"UDRW";
+const VERSION: u8 = 1;
+
+// Cells from before they held grapheme clusters.
+#[derive(Deserialize)]
+struct CharCharacter {
+    character: char,
+    character_transparent: bool,
+    continuation: bool,
+    style: Style
+}
+
+impl From<CharCharacter> for Character {
+    fn from(c: CharCharacter) -> Self {
+        if c.continuation {
+            Character::new_continuation(c.style)
+        } else if c.character_transparent {
+            Character::new_transparent(c.style)
+        } else {
+            Character::new(c.character, c.style)
+        }
+    }
+}
+
+// Cells from before transparency and wide glyphs.
+#[derive(Deserialize)]
+struct PlainCharacter {
+    character: char,
+    style: Style
+}
+
+impl From<PlainCharacter> for Character {
+    fn from(c: PlainCharacter) -> Self {
+        Character::new(c.character, c.style)
+    }
+}
+
+// The layouts only differ in length, so a layout has to use up all of the data to count as a match.
+fn decode_exact<T: DeserializeOwned>(data: &[u8]) -> Option<Vec<Vec<T>>> {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+        .deserialize(data)
+        .ok()
+}
+
+fn convert<T: Into<Character>>(data: Vec<Vec<T>>) -> Vec<Vec<Character>> {
+    data.into_iter().map(|r| r.into_iter().map(Into::into).collect()).collect()
+}
 
 pub struct LoaderBin {}
 
 impl Importer<&[u8]> for LoaderBin {
     fn import(data: &[u8]) -> Vec<Vec<Character>> {
-        bincode::deserialize(data).unwrap()
+        match data.strip_prefix(MAGIC) {
+            Some([VERSION, cells @ ..]) => bincode::deserialize(cells).unwrap(),
+            Some(_) => panic!("Unsupported canvas file version"),
+            None => decode_exact::<CharCharacter>(data).map(convert)
+                .or_else(|| decode_exact::<PlainCharacter>(data).map(convert))
+                .expect("Canvas file should hold one of the known layouts")
+        }
     }
 }
 
 impl Exporter<Vec<u8>> for LoaderBin {
     fn export(data: &Vec<Vec<Character>>) -> Vec<u8> {
-        bincode::serialize(data).unwrap()
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bytes.extend(bincode::serialize(data).unwrap());
+        bytes
     }
 }
 
-
 #[cfg(test)]
 mod test {
     use crate::{draw::{character::Character, style::{Style, Color, Modifiers}}, loaders::loader::{Exporter, Importer}};
@@ -46,12 +106,59 @@ mod test {
                 Character::new('!', Style::new(Color::DarkCyan,    Color::DarkBlue,    Modifiers::new(true,  true,  false))),
                 Character::new('!', Style::new(Color::DarkGreen,   Color::Cyan,        Modifiers::new(true,  true,  false))),
                 Character::new('!', Style::new(Color::Cyan,        Color::Red,         Modifiers::new(true,  true,  true ))),
+            ],
+            vec![
+                Character::transparent(),
+                Character::new_transparent(Style::new(Color::Red, Color::Transparent, Modifiers::new(true, false, false))),
+                Character::new('?', Style::new(Color::Transparent, Color::Rgb { r: 1, g: 2, b: 3 }, Modifiers::default())),
+                Character::new("e\u{301}", Style::default()),
+                Character::new("👩\u{200D}👩\u{200D}👧\u{200D}👦", Style::default()),
+                Character::new_continuation(Style::default()),
+                Character::default(),
+                Character::default(),
             ]
         ];
 
         let exported = LoaderBin::export(&data);
+        assert!(exported.starts_with(b"UDRW\x01"));
         let imported = LoaderBin::import(&exported);
 
         assert_eq!(data, imported)
     }
+
+    #[test]
+    fn test_loader_bin_import_char_layout() {
+        let style = Style::new(Color::Red, Color::None, Modifiers::new(true, false, false));
+        let old = vec![vec![('漢', false, false, style), (' ', false, true, style), (' ', true, false, style), ('a', false, false, Style::default())]];
+
+        assert_eq!(
+            LoaderBin::import(&bincode::serialize(&old).unwrap()),
+            vec![vec![
+                Character::new('漢', style),
+                Character::new_continuation(style),
+                Character::new_transparent(style),
+                Character::new('a', Style::default())
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_loader_bin_import_plain_layout() {
+        let style = Style::new(Color::Rgb { r: 1, g: 2, b: 3 }, Color::DarkBlue, Modifiers::default());
+        let old = vec![vec![('H', style), ('i', Style::default())], vec![(' ', style), ('!', style)]];
+
+        assert_eq!(
+            LoaderBin::import(&bincode::serialize(&old).unwrap()),
+            vec![
+                vec![Character::new('H', style), Character::new('i', Style::default())],
+                vec![Character::new(' ', style), Character::new('!', style)]
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Unsupported canvas file version")]
+    fn test_loader_bin_import_unknown_version() {
+        LoaderBin::import(b"UDRW\x09");
+    }
 }
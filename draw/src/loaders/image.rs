@@ -0,0 +1,535 @@
+use std::fmt::Display;
+
+use super::loader::*;
+use crate::draw::{
+    character::Character,
+    ellipse::CELL_ASPECT,
+    pixel::block::{BlockCanvas, BlockMode},
+    quantize::{self, QuantizeTarget},
+    style::{Color, Modifiers, Style}
+};
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq
+)]
+pub enum ImageError {
+    Unsupported,
+    InvalidHeader,
+    UnexpectedEnd
+}
+
+impl Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageError::Unsupported => write!(f, "Unsupported image format"),
+            ImageError::InvalidHeader => write!(f, "Invalid image header"),
+            ImageError::UnexpectedEnd => write!(f, "Image data ended early")
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq
+)]
+pub enum ImageStrategy {
+    Ramp(Vec<char>),
+    HalfBlock,
+    Palette(Vec<Color>)
+}
+
+impl ImageStrategy {
+    pub fn ramp() -> Self {
+        ImageStrategy::Ramp(" .:-=+*#%@".chars().collect())
+    }
+
+    pub fn ansi_palette() -> Self {
+        ImageStrategy::Palette(QuantizeTarget::Named.colors())
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq
+)]
+pub struct ImageOptions {
+    pub width: Option<usize>,
+    pub strategy: ImageStrategy
+}
+
+impl ImageOptions {
+    pub fn new(width: Option<usize>, strategy: ImageStrategy) -> Self {
+        Self {
+            width,
+            strategy
+        }
+    }
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        Self::new(
+            None,
+            ImageStrategy::HalfBlock
+        )
+    }
+}
+
+
+// RGBA pixels, row by row.
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<[u8; 4]>
+}
+
+impl Image {
+    // Averages the pixels covered by the given area, which is in pixel units.
+    fn sample(&self, x: f64, y: f64, width: f64, height: f64) -> [u8; 4] {
+        let x0 = (x as usize).min(self.width - 1);
+        let y0 = (y as usize).min(self.height - 1);
+        let x1 = ((x + width) as usize).clamp(x0 + 1, self.width);
+        let y1 = ((y + height) as usize).clamp(y0 + 1, self.height);
+
+        let mut sum = [0; 4];
+        for py in y0..y1 {
+            for p in &self.pixels[py * self.width + x0..py * self.width + x1] {
+                for (s, v) in sum.iter_mut().zip(p) {
+                    *s += *v as usize;
+                }
+            }
+        }
+        let count = (x1 - x0) * (y1 - y0);
+        sum.map(|s| (s / count) as u8)
+    }
+}
+
+fn skip_whitespace(data: &[u8], mut i: usize) -> usize {
+    loop {
+        match data.get(i) {
+            Some(b'#') => while data.get(i).is_some_and(|c| *c != b'\n') {
+                i += 1;
+            },
+            Some(c) if c.is_ascii_whitespace() => i += 1,
+            _ => return i
+        }
+    }
+}
+
+fn read_number(data: &[u8], i: &mut usize) -> Result<usize, ImageError> {
+    *i = skip_whitespace(data, *i);
+    let start = *i;
+    while data.get(*i).is_some_and(u8::is_ascii_digit) {
+        *i += 1;
+    }
+    if start == *i {
+        return Err(if *i >= data.len() { ImageError::UnexpectedEnd } else { ImageError::InvalidHeader });
+    }
+    std::str::from_utf8(&data[start..*i]).unwrap_or_default().parse().map_err(|_| ImageError::InvalidHeader)
+}
+
+fn checked_size(width: usize, height: usize, bytes: usize) -> Result<usize, ImageError> {
+    width.checked_mul(height)
+        .and_then(|s| s.checked_mul(bytes))
+        .ok_or(ImageError::InvalidHeader)
+}
+
+fn to_pixel(samples: &[usize], maxval: usize) -> [u8; 4] {
+    let scale = |v: usize| (v.min(maxval) * 255 / maxval) as u8;
+    match samples {
+        [v] => [scale(*v), scale(*v), scale(*v), 255],
+        [v, a] => [scale(*v), scale(*v), scale(*v), scale(*a)],
+        [r, g, b] => [scale(*r), scale(*g), scale(*b), 255],
+        [r, g, b, a, ..] => [scale(*r), scale(*g), scale(*b), scale(*a)],
+        [] => [0, 0, 0, 255]
+    }
+}
+
+fn read_samples(data: &[u8], i: usize, size: (usize, usize), depth: usize, maxval: usize, ascii: bool) -> Result<Image, ImageError> {
+    if maxval == 0 || maxval > 65535 || size.0 == 0 || size.1 == 0 {
+        return Err(ImageError::InvalidHeader);
+    }
+
+    let count = checked_size(size.0, size.1, depth)?;
+    let samples: Vec<usize> = if ascii {
+        let mut i = i;
+        let mut samples = Vec::new();
+        for _ in 0..count {
+            samples.push(read_number(data, &mut i)?);
+        }
+        samples
+    } else {
+        let bytes = if maxval < 256 { 1 } else { 2 };
+        let raster = data.get(i..).ok_or(ImageError::UnexpectedEnd)?;
+        if raster.len() < count.checked_mul(bytes).ok_or(ImageError::InvalidHeader)? {
+            return Err(ImageError::UnexpectedEnd);
+        }
+        raster.chunks(bytes).take(count).map(|s| s.iter().fold(0, |v, b| v << 8 | *b as usize)).collect()
+    };
+
+    Ok(Image {
+        width: size.0,
+        height: size.1,
+        pixels: samples.chunks(depth).map(|s| to_pixel(s, maxval)).collect()
+    })
+}
+
+fn decode_pnm(data: &[u8]) -> Result<Image, ImageError> {
+    let (depth, ascii) = match &data[..2] {
+        b"P2" => (1, true),
+        b"P3" => (3, true),
+        b"P5" => (1, false),
+        b"P6" => (3, false),
+        _ => return Err(ImageError::Unsupported)
+    };
+
+    let mut i = 2;
+    let width = read_number(data, &mut i)?;
+    let height = read_number(data, &mut i)?;
+    let maxval = read_number(data, &mut i)?;
+    // Binary rasters start after exactly one whitespace byte.
+    let start = if ascii { i } else { i + 1 };
+    read_samples(data, start, (width, height), depth, maxval, ascii)
+}
+
+fn decode_pam(data: &[u8]) -> Result<Image, ImageError> {
+    let (mut width, mut height, mut depth, mut maxval) = (0, 0, 0, 0);
+    let mut i = 2;
+    loop {
+        let end = data[i..].iter().position(|c| *c == b'\n').ok_or(ImageError::UnexpectedEnd)? + i;
+        let line = std::str::from_utf8(&data[i..end]).map_err(|_| ImageError::InvalidHeader)?;
+        i = end + 1;
+
+        let mut tokens = line.split_whitespace();
+        let value = |tokens: &mut std::str::SplitWhitespace| -> Result<usize, ImageError> {
+            tokens.next().and_then(|t| t.parse().ok()).ok_or(ImageError::InvalidHeader)
+        };
+        match tokens.next() {
+            Some("WIDTH") => width = value(&mut tokens)?,
+            Some("HEIGHT") => height = value(&mut tokens)?,
+            Some("DEPTH") => depth = value(&mut tokens)?,
+            Some("MAXVAL") => maxval = value(&mut tokens)?,
+            Some("ENDHDR") => break,
+            _ => ()
+        }
+    }
+
+    if !(1..=4).contains(&depth) {
+        return Err(ImageError::Unsupported);
+    }
+    read_samples(data, i, (width, height), depth, maxval, false)
+}
+
+fn decode_bmp(data: &[u8]) -> Result<Image, ImageError> {
+    let u16_at = |i: usize| data.get(i..i + 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as usize).ok_or(ImageError::UnexpectedEnd);
+    let u32_at = |i: usize| data.get(i..i + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]])).ok_or(ImageError::UnexpectedEnd);
+
+    let offset = u32_at(10)? as usize;
+    let header = u32_at(14)? as usize;
+    if header < 40 {
+        return Err(ImageError::Unsupported);
+    }
+    let width = u32_at(18)? as i32;
+    let height = u32_at(22)? as i32;
+    let bpp = u16_at(28)?;
+    let compression = u32_at(30)?;
+    match (compression, bpp) {
+        (0, 1 | 4 | 8 | 24 | 32) | (3, 32) => (),
+        _ => return Err(ImageError::Unsupported)
+    }
+    if width <= 0 || height == 0 {
+        return Err(ImageError::InvalidHeader);
+    }
+
+    let (width, top_down) = (width as usize, height < 0);
+    let height = height.unsigned_abs() as usize;
+    let colors = match u32_at(46)? as usize {
+        0 if bpp <= 8 => 1 << bpp,
+        n => n.min(256)
+    };
+    let palette: Vec<[u8; 4]> = (0..if bpp <= 8 { colors } else { 0 }).map(|c| {
+        let i = 14 + header + c * 4;
+        data.get(i..i + 3).map(|p| [p[2], p[1], p[0], 255]).ok_or(ImageError::UnexpectedEnd)
+    }).collect::<Result<_, _>>()?;
+
+    let stride = checked_size(width, bpp, 1)?.div_ceil(32) * 4;
+    if data.len() < offset.saturating_add(checked_size(stride, height, 1)?) {
+        return Err(ImageError::UnexpectedEnd);
+    }
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let row = if top_down { y } else { height - 1 - y };
+        let row = &data[offset + row * stride..offset + (row + 1) * stride];
+        for x in 0..width {
+            let pixel = match bpp {
+                24 | 32 => {
+                    let p = &row[x * bpp / 8..];
+                    // Plain 32 bit bitmaps leave the alpha byte unused.
+                    [p[2], p[1], p[0], if bpp == 32 && compression == 3 { p[3] } else { 255 }]
+                },
+                _ => {
+                    let bit = x * bpp;
+                    let index = (row[bit / 8] >> (8 - bpp - bit % 8)) & ((1 << bpp) - 1) as u8;
+                    *palette.get(index as usize).ok_or(ImageError::InvalidHeader)?
+                }
+            };
+            pixels.push(pixel);
+        }
+    }
+
+    Ok(Image { width, height, pixels })
+}
+
+fn decode(data: &[u8]) -> Result<Image, ImageError> {
+    match data.get(..2) {
+        Some(b"BM") => decode_bmp(data),
+        Some(b"P7") => decode_pam(data),
+        Some([b'P', _]) => decode_pnm(data),
+        Some(_) => Err(ImageError::Unsupported),
+        None => Err(ImageError::UnexpectedEnd)
+    }
+}
+
+fn luminance(pixel: [u8; 4]) -> f64 {
+    (0.2126 * pixel[0] as f64 + 0.7152 * pixel[1] as f64 + 0.0722 * pixel[2] as f64) * pixel[3] as f64 / 255.0 / 255.0
+}
+
+pub struct LoaderImage {}
+
+impl LoaderImage {
+    pub fn import_with(data: &[u8], options: &ImageOptions) -> Result<Vec<Vec<Character>>, ImageError> {
+        let image = decode(data)?;
+        let width = options.width.unwrap_or(image.width).max(1);
+        let scale = image.width as f64 / width as f64;
+        // Cells are taller than they are wide, so each one covers more image rows than columns.
+        let height = ((image.height as f64 / scale / CELL_ASPECT as f64).round() as usize).max(1);
+        let cell_height = image.height as f64 / height as f64;
+
+        let ramp = match &options.strategy {
+            ImageStrategy::Ramp(ramp) => ramp,
+            _ => {
+                let palette = match &options.strategy {
+                    ImageStrategy::Palette(palette) => quantize::resolve(palette),
+                    _ => Vec::new()
+                };
+                let mut blocks = BlockCanvas::new(BlockMode::HalfBlock, width, height);
+                for y in 0..height * 2 {
+                    for x in 0..width {
+                        let pixel = image.sample(x as f64 * scale, y as f64 * cell_height / 2.0, scale, cell_height / 2.0);
+                        let color = match &options.strategy {
+                            _ if pixel[3] < 128 => Color::Transparent,
+                            ImageStrategy::Palette(_) => quantize::nearest(&palette, [pixel[0], pixel[1], pixel[2]]).map_or(Color::None, |(c, _)| c),
+                            _ => Color::Rgb { r: pixel[0], g: pixel[1], b: pixel[2] }
+                        };
+                        blocks.set_pixel(x, y, color);
+                    }
+                }
+
+                return Ok((0..height).map(|y| {
+                    (0..width).map(|x| blocks.cell(x, y).unwrap_or_default()).collect()
+                }).collect());
+            }
+        };
+
+        Ok((0..height).map(|y| {
+            (0..width).map(|x| {
+                let pixel = image.sample(x as f64 * scale, y as f64 * cell_height, scale, cell_height);
+                let level = (luminance(pixel) * ramp.len().saturating_sub(1) as f64).round() as usize;
+                Character::new(ramp.get(level).copied().unwrap_or(' '), Style::new(Color::None, Color::None, Modifiers::default()))
+            }).collect()
+        }).collect())
+    }
+}
+
+impl TryImporter<&[u8]> for LoaderImage {
+    type Error = ImageError;
+
+    fn try_import(data: &[u8]) -> Result<Vec<Vec<Character>>, ImageError> {
+        Self::import_with(data, &ImageOptions::default())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::loaders::txt::LoaderTxt;
+
+    use super::*;
+
+    fn import(data: &[u8]) -> Vec<Vec<Character>> {
+        LoaderImage::try_import(data).unwrap()
+    }
+
+    fn ramp(data: &[u8], width: Option<usize>) -> String {
+        LoaderTxt::export(&LoaderImage::import_with(data, &ImageOptions::new(width, ImageStrategy::ramp())).unwrap())
+    }
+
+    fn bmp(width: i32, height: i32, bpp: u16, palette: &[[u8; 4]], rows: &[&[u8]]) -> Vec<u8> {
+        let offset = 54 + palette.len() * 4;
+        let mut data = b"BM".to_vec();
+        data.extend(0u32.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        data.extend((offset as u32).to_le_bytes());
+        data.extend(40u32.to_le_bytes());
+        data.extend(width.to_le_bytes());
+        data.extend(height.to_le_bytes());
+        data.extend(1u16.to_le_bytes());
+        data.extend(bpp.to_le_bytes());
+        data.extend([0; 16]);
+        data.extend((palette.len() as u32).to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        for p in palette {
+            data.extend(p);
+        }
+        for r in rows {
+            data.extend(*r);
+        }
+        data
+    }
+
+    #[test]
+    fn test_loader_image_pgm() {
+        let ascii = b"P2\n# comment\n4 2\n255\n0 85 170 255\n0 85 170 255\n";
+        let binary = [b"P5 4 2 255\n".as_slice(), &[0, 85, 170, 255, 0, 85, 170, 255]].concat();
+
+        assert_eq!(ramp(ascii, None), " -*@");
+        assert_eq!(ramp(&binary, None), " -*@");
+    }
+
+    #[test]
+    fn test_loader_image_ppm() {
+        let ascii = b"P3 2 2 15\n15 0 0  0 0 15\n15 0 0  0 0 15\n";
+        let data = LoaderImage::import_with(ascii, &ImageOptions::default()).unwrap();
+
+        assert_eq!(
+            data,
+            vec![vec![
+                Character::new('█', Style::new(Color::Rgb { r: 255, g: 0, b: 0 }, Color::Transparent, Modifiers::default())),
+                Character::new('█', Style::new(Color::Rgb { r: 0, g: 0, b: 255 }, Color::Transparent, Modifiers::default()))
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_loader_image_ppm_16_bit() {
+        let binary = [b"P6 1 2 65535\n".as_slice(), &[255, 255, 0, 0, 0, 0, 0, 0, 0, 0, 255, 255]].concat();
+
+        assert_eq!(
+            import(&binary),
+            vec![vec![
+                Character::new('▀', Style::new(Color::Rgb { r: 255, g: 0, b: 0 }, Color::Rgb { r: 0, g: 0, b: 255 }, Modifiers::default()))
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_loader_image_pam() {
+        let data = [
+            b"P7\nWIDTH 1\nHEIGHT 2\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n".as_slice(),
+            &[0, 255, 0, 255, 0, 0, 0, 0]
+        ].concat();
+
+        assert_eq!(
+            import(&data),
+            vec![vec![
+                Character::new('▀', Style::new(Color::Rgb { r: 0, g: 255, b: 0 }, Color::Transparent, Modifiers::default()))
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_loader_image_bmp_24() {
+        // Rows are stored bottom up and padded to four bytes.
+        let data = bmp(2, 2, 24, &[], &[
+            &[255, 0, 0, 255, 0, 0, 0, 0],
+            &[0, 0, 255, 0, 0, 255, 0, 0]
+        ]);
+
+        assert_eq!(
+            import(&data),
+            vec![vec![
+                Character::new('▀', Style::new(Color::Rgb { r: 255, g: 0, b: 0 }, Color::Rgb { r: 0, g: 0, b: 255 }, Modifiers::default())),
+                Character::new('▀', Style::new(Color::Rgb { r: 255, g: 0, b: 0 }, Color::Rgb { r: 0, g: 0, b: 255 }, Modifiers::default()))
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_loader_image_bmp_palette() {
+        let data = bmp(4, -2, 1, &[[0, 0, 0, 0], [255, 255, 255, 0]], &[
+            &[0b1010_0000, 0, 0, 0],
+            &[0b1010_0000, 0, 0, 0]
+        ]);
+        assert_eq!(ramp(&data, None), "@ @ ");
+
+        let data = bmp(2, 2, 4, &[[0, 0, 0, 0], [255, 255, 255, 0]], &[
+            &[0x01, 0, 0, 0],
+            &[0x10, 0, 0, 0]
+        ]);
+        assert_eq!(ramp(&data, None), "==");
+    }
+
+    #[test]
+    fn test_loader_image_width() {
+        let mut data = b"P2 8 8 1\n".to_vec();
+        for y in 0..8 {
+            for x in 0..8 {
+                data.extend(if x < 4 && y < 4 { b"1 " } else { b"0 " });
+            }
+        }
+
+        assert_eq!(ramp(&data, Some(4)), "@@  \n    ");
+        assert_eq!(ramp(&data, Some(2)), "= ");
+        assert_eq!(ramp(&data, None), "@@@@    \n@@@@    \n        \n        ");
+    }
+
+    #[test]
+    fn test_loader_image_palette() {
+        let data = b"P3 2 2 255\n250 10 10  10 10 10\n250 10 10  10 10 10\n";
+        let strategy = ImageStrategy::Palette(vec![Color::Red, Color::Black, Color::Rgb { r: 9, g: 9, b: 9 }]);
+
+        assert_eq!(
+            LoaderImage::import_with(data, &ImageOptions::new(None, strategy)).unwrap(),
+            vec![vec![
+                Character::new('█', Style::new(Color::Red, Color::Transparent, Modifiers::default())),
+                Character::new('█', Style::new(Color::Rgb { r: 9, g: 9, b: 9 }, Color::Transparent, Modifiers::default()))
+            ]]
+        );
+        assert_eq!(
+            LoaderImage::import_with(data, &ImageOptions::new(None, ImageStrategy::ansi_palette())).unwrap()[0][1].style().fg,
+            Color::Black
+        );
+
+        let indexed = ImageStrategy::Palette(vec![Color::Indexed { i: 196 }, Color::Indexed { i: 244 }]);
+        assert_eq!(
+            LoaderImage::import_with(data, &ImageOptions::new(None, indexed)).unwrap()[0][0].style().fg,
+            Color::Indexed { i: 196 }
+        );
+    }
+
+    #[test]
+    fn test_loader_image_errors() {
+        assert_eq!(LoaderImage::try_import(b"").unwrap_err(), ImageError::UnexpectedEnd);
+        assert_eq!(LoaderImage::try_import(b"GIF89a").unwrap_err(), ImageError::Unsupported);
+        assert_eq!(LoaderImage::import_with(b"P4 1 1\n\0", &ImageOptions::default()).unwrap_err(), ImageError::Unsupported);
+        assert_eq!(LoaderImage::import_with(b"P5 2 2 255\n\0\0", &ImageOptions::default()).unwrap_err(), ImageError::UnexpectedEnd);
+        assert_eq!(LoaderImage::import_with(b"P5 0 2 255\n", &ImageOptions::default()).unwrap_err(), ImageError::InvalidHeader);
+        assert_eq!(LoaderImage::import_with(b"P5 x 2 255\n", &ImageOptions::default()).unwrap_err(), ImageError::InvalidHeader);
+        assert_eq!(
+            LoaderImage::import_with(b"P6 99999999999 99999999999 255\n", &ImageOptions::default()).unwrap_err(),
+            ImageError::InvalidHeader
+        );
+        assert_eq!(
+            LoaderImage::import_with(&bmp(2, 2, 24, &[], &[&[0; 8]]), &ImageOptions::default()).unwrap_err(),
+            ImageError::UnexpectedEnd
+        );
+    }
+}
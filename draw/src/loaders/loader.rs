@@ -4,6 +4,13 @@ pub trait Importer<T> {
     fn import(data: T) -> Vec<Vec<Character>>;
 }
 
+// For formats whose data can be invalid, instead of panicking on it.
+pub trait TryImporter<T> {
+    type Error;
+
+    fn try_import(data: T) -> Result<Vec<Vec<Character>>, Self::Error>;
+}
+
 pub trait Exporter<T> {
     fn export(data: &Vec<Vec<Character>>) -> T;
 }
@@ -1,3 +1,5 @@
+use unicode_segmentation::UnicodeSegmentation;
+
 use super::loader::*;
 use crate::draw::{character::Character, style::Style};
 
@@ -5,13 +7,19 @@ pub struct LoaderTxt {}
 
 impl Importer<&str> for LoaderTxt {
     fn import(data: &str) -> Vec<Vec<Character>> {
-        let width = data.lines().map(|l| l.len()).max().unwrap();
-
-        data.lines().map(|l| {
-            let mut l: Vec<_> = l.chars().map(|c|
-                Character::new(c, Style::default())
-            ).collect();
+        let lines: Vec<Vec<_>> = data.lines().map(|l| {
+            l.graphemes(true).flat_map(|c| {
+                let c = Character::new(c, Style::default());
+                if c.is_wide() {
+                    vec![c, Character::new_continuation(Style::default())]
+                } else {
+                    vec![c]
+                }
+            }).collect()
+        }).collect();
+        let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
 
+        lines.into_iter().map(|mut l| {
             l.resize(width, Character::default());
             l
         }).collect()
@@ -21,8 +29,14 @@ impl Importer<&str> for LoaderTxt {
 impl Exporter<String> for LoaderTxt {
     fn export(data: &Vec<Vec<Character>>) -> String {
         data.iter().map(|l| {
-            l.iter().map(|c| {
-                c.character()
+            l.iter().enumerate().filter_map(|(x, c)| {
+                if c.is_continuation() && x > 0 && l[x - 1].is_wide() {
+                    None
+                } else if c.is_character_transparent() || c.is_continuation() {
+                    Some(" ")
+                } else {
+                    Some(c.grapheme())
+                }
             }).collect::<String>()
         }).collect::<Vec<String>>().join("\n")
     }
@@ -64,6 +78,67 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_loader_txt_import_empty() {
+        assert!(LoaderTxt::import("").is_empty());
+    }
+
+    #[test]
+    fn test_loader_txt_import_wide() {
+        assert_eq!(
+            LoaderTxt::import("a漢\né"),
+            vec![
+                vec![
+                    Character::new('a', Style::default()),
+                    Character::new('漢', Style::default()),
+                    Character::new_continuation(Style::default()),
+                ],
+                vec![
+                    Character::new('é', Style::default()),
+                    Character::new(' ', Style::default()),
+                    Character::new(' ', Style::default()),
+                ]
+            ]
+        )
+    }
+
+    #[test]
+    fn test_loader_txt_export_wide() {
+        assert_eq!(
+            LoaderTxt::export(&vec![
+                vec![
+                    Character::new('漢', Style::default()),
+                    Character::new_continuation(Style::default()),
+                    Character::new_continuation(Style::default()),
+                    Character::new('b', Style::default()),
+                ]
+            ]),
+            "漢 b"
+        );
+        assert_eq!(LoaderTxt::export(&LoaderTxt::import("😀x\n字字")), "😀x \n字字");
+    }
+
+    #[test]
+    fn test_loader_txt_import_graphemes() {
+        assert_eq!(
+            LoaderTxt::import("e\u{301}x\n👩\u{200D}💻"),
+            vec![
+                vec![
+                    Character::new("e\u{301}", Style::default()),
+                    Character::new('x', Style::default()),
+                ],
+                vec![
+                    Character::new("👩\u{200D}💻", Style::default()),
+                    Character::new_continuation(Style::default()),
+                ]
+            ]
+        );
+        assert_eq!(
+            LoaderTxt::export(&LoaderTxt::import("e\u{301}x\n👩\u{200D}💻")),
+            "e\u{301}x\n👩\u{200D}💻"
+        );
+    }
+
     #[test]
     fn test_loader_txt_export() {
         assert_eq!(
@@ -92,4 +167,19 @@ mod test {
             "Hello   \nworld!!!"
         );
     }
+
+    #[test]
+    fn test_loader_txt_export_transparent() {
+        assert_eq!(
+            LoaderTxt::export(&vec![
+                vec![
+                    Character::new('a', Style::default()),
+                    Character::transparent(),
+                    Character::new_transparent(Style::new(Color::Red, Color::Black, Modifiers::default())),
+                    Character::new('b', Style::transparent()),
+                ]
+            ]),
+            "a  b"
+        );
+    }
 }
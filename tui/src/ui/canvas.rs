@@ -25,10 +25,12 @@ impl Canvas {
 
 impl Widget for Canvas {
     fn render(self, area: Rect, buf: &mut tui::buffer::Buffer) {
-       let lines = self.canvas.buffer().iter()
+       let lines = self.canvas.rows()
             .map(|l| {
-                let text = Spans::from(l.iter()
-                    .map(|c| CharacterMapping::from(c).into())
+                // The terminal already draws wide glyphs over two columns.
+                let text = Spans::from(l.iter().enumerate()
+                    .filter(|(x, c)| !(c.is_continuation() && *x > 0 && l[x - 1].is_wide()))
+                    .map(|(_, c)| CharacterMapping::from(c).into())
                     .collect::<Vec<_>>(),
                 );
                 Paragraph::new(text)
@@ -30,7 +30,8 @@ impl From<&DrawColor> for ColorMapping {
             DrawColor::DarkCyan => TuiColor::Cyan,
             DrawColor::White => TuiColor::White,
             DrawColor::Indexed { i } => TuiColor::Indexed(i.clone()),
-            DrawColor::Rgb { r, g, b } => TuiColor::Rgb(r.clone(), g.clone(), b.clone())
+            DrawColor::Rgb { r, g, b } => TuiColor::Rgb(r.clone(), g.clone(), b.clone()),
+            DrawColor::Transparent => TuiColor::Reset
         })
     }
 }
@@ -102,8 +103,14 @@ impl<'a> Into<TuiSpan<'a>> for CharacterMapping<'a> {
 
 impl<'a> From<&DrawCharacter> for CharacterMapping<'a> {
     fn from(character: &DrawCharacter) -> Self {
+        let text = if character.is_character_transparent() || character.is_continuation() {
+            " "
+        } else {
+            character.grapheme()
+        };
+
         CharacterMapping(TuiSpan::styled(
-            character.character().to_string(),
+            text.to_string(),
             StyleMapping::from(character.style()).into()
         ))
     }